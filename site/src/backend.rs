@@ -169,6 +169,9 @@ impl SysBackend for WebBackend {
     fn is_file(&self, path: &str) -> Result<bool, String> {
         Ok(self.file(path.as_ref(), |_| {}).is_ok())
     }
+    fn file_size(&self, path: &str) -> Result<u64, String> {
+        self.file(path.as_ref(), |contents| contents.len() as u64)
+    }
     fn file_exists(&self, path: &str) -> bool {
         self.file(path.as_ref(), |_| {}).is_ok()
     }
@@ -312,6 +315,9 @@ impl SysBackend for WebBackend {
     fn now(&self) -> f64 {
         *START_TIME.get_or_init(|| 0.0) + now()
     }
+    fn cpu_count(&self) -> Result<usize, String> {
+        Ok(window().navigator().hardware_concurrency() as usize)
+    }
     fn set_clipboard(&self, contents: &str) -> Result<(), String> {
         _ = window().navigator().clipboard().write_text(contents);
         Ok(())
@@ -420,9 +420,39 @@ fn prim_docs() {
     for prim in Primitive::non_deprecated() {
         for line in &prim.doc().lines {
             if let PrimDocLine::Example(ex) = line {
-                if ["&sl", "&tcpc", "&tlsc", "&ast", "&clset", "&frab", "&fmd"]
-                    .iter()
-                    .any(|prim| ex.input().contains(prim))
+                if [
+                    "&sl",
+                    "&tcpc",
+                    "&tlsc",
+                    "&ast",
+                    "&clset",
+                    "&frab",
+                    "&fmd",
+                    "&frmd",
+                    "&fglob",
+                    "&sh",
+                    "&fhash",
+                    "&frjl",
+                    "&fcan",
+                    "&fabs",
+                    "&fwjl",
+                    "&ftmpf",
+                    "&ftmpd",
+                    "&fsize",
+                    "&fwatch",
+                    "&fzipr",
+                    "&fzipw",
+                    "&httpgetall",
+                    "&jwtver",
+                    "&measurens",
+                    "&cpucount",
+                    "&cwd",
+                    "&ftree",
+                    "&frcsv",
+                    "&fwcsv",
+                ]
+                .iter()
+                .any(|prim| ex.input().contains(prim))
                 {
                     continue;
                 }
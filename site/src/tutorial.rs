@@ -1726,6 +1726,7 @@ fn TutorialModules() -> impl IntoView {
         <p>"If we have a lot of items to import, we can use multiple lines."</p>
         <Editor example="~ \"example.ua\"\n~ Increment Square\n~ Span\n~ Foo Bar\n\nIncrement Square Foo\nSpan 4 10"/>
         <p>"The formatter will automatically indent the imports if they are on multiple lines. It will also alphabetize them. Try it out!"</p>
+        <p>"If the path is a directory, the first of "<code>"lib.ua"</code>", "<code>"main.ua"</code>", or "<code>"index.ua"</code>" that exists in it is imported. This lets you organize a module into multiple files in a directory. Imports in the entry file are relative to the directory."</p>
 
         <Hd id="binding">"Binding Modules"</Hd>
         <p>"If we put a name before the import, we can bind the module to that name."</p>
@@ -186,25 +186,30 @@ pub fn rgb_image_to_array(image: image::RgbImage) -> Array<f64> {
 #[doc(hidden)]
 #[cfg(feature = "image")]
 pub fn image_bytes_to_array(bytes: &[u8], alpha: bool) -> Result<Array<f64>, String> {
+    let image =
+        image::load_from_memory(bytes).map_err(|e| format!("Failed to read image: {}", e))?;
     Ok(if alpha {
-        let image = image::load_from_memory(bytes)
-            .map_err(|e| format!("Failed to read image: {}", e))?
-            .into_rgba8();
-        let shape = crate::Shape::from([image.height() as usize, image.width() as usize, 4]);
-        Array::new(
-            shape,
-            (image.into_raw().into_iter())
-                .map(|b| b as f64 / 255.0)
-                .collect::<crate::cowslice::CowSlice<_>>(),
-        )
+        value_from_image(image)
     } else {
-        let image = image::load_from_memory(bytes)
-            .map_err(|e| format!("Failed to read image: {}", e))?
-            .into_rgb8();
-        rgb_image_to_array(image)
+        rgb_image_to_array(image.into_rgb8())
     })
 }
 
+/// Convert an image into an HxWx4 RGBA array with values in the range 0-1
+///
+/// This is the inverse of [`value_to_image`].
+#[cfg(feature = "image")]
+pub fn value_from_image(image: DynamicImage) -> Array<f64> {
+    let image = image.into_rgba8();
+    let shape = crate::Shape::from([image.height() as usize, image.width() as usize, 4]);
+    Array::new(
+        shape,
+        (image.into_raw().into_iter())
+            .map(|b| b as f64 / 255.0)
+            .collect::<crate::cowslice::CowSlice<_>>(),
+    )
+}
+
 #[doc(hidden)]
 #[cfg(feature = "image")]
 pub fn image_to_bytes(image: &DynamicImage, format: ImageOutputFormat) -> Result<Vec<u8>, String> {
@@ -220,15 +225,21 @@ pub fn image_to_bytes(image: &DynamicImage, format: ImageOutputFormat) -> Result
 pub fn value_to_image(value: &Value) -> Result<DynamicImage, String> {
     if ![2, 3].contains(&value.rank()) {
         return Err(format!(
-            "Image must be a rank 2 or 3 numeric array, but it is a rank-{} {} array",
+            "Image must be a rank 2 or 3 numeric array, but it is a rank-{} {} array with shape {}",
             value.rank(),
-            value.type_name()
+            value.type_name(),
+            value.shape()
         ));
     }
     let bytes = match value {
         Value::Num(nums) => nums.data.iter().map(|f| (*f * 255.0) as u8).collect(),
         Value::Byte(bytes) => bytes.data.iter().map(|&b| (b > 0) as u8 * 255).collect(),
-        _ => return Err("Image must be a numeric array".into()),
+        _ => {
+            return Err(format!(
+                "Image must be a numeric array, but it is {}",
+                value.type_name_plural()
+            ))
+        }
     };
     #[allow(clippy::match_ref_pats)]
     let [height, width, px_size] = match value.shape().dims() {
@@ -251,7 +262,9 @@ pub fn value_to_image(value: &Value) -> Result<DynamicImage, String> {
             .into(),
         n => {
             return Err(format!(
-                "For a color image, the last dimension of the image array must be between 1 and 4 but it is {n}"
+                "For a color image, the last dimension of the image array must be between 1 and 4, \
+                but it is {n} in an array with shape {}",
+                value.shape()
             ))
         }
     })
@@ -262,7 +275,12 @@ pub fn value_to_sample(audio: &Value) -> Result<Vec<[f32; 2]>, String> {
     let unrolled: Vec<f32> = match audio {
         Value::Num(nums) => nums.data.iter().map(|&f| f as f32).collect(),
         Value::Byte(byte) => byte.data.iter().map(|&b| b as f32).collect(),
-        _ => return Err("Audio must be a numeric array".into()),
+        _ => {
+            return Err(format!(
+                "Audio must be a numeric array, but it is {}",
+                audio.type_name_plural()
+            ))
+        }
     };
     let (length, mut channels) = match audio.rank() {
         1 => (unrolled.len(), vec![unrolled]),
@@ -275,7 +293,8 @@ pub fn value_to_sample(audio: &Value) -> Result<Vec<[f32; 2]>, String> {
         ),
         n => {
             return Err(format!(
-                "Audio must be a rank 1 or 2 numeric array, but it is rank {n}"
+                "Audio must be a rank 1 or 2 numeric array, but it is rank {n} with shape {}",
+                audio.shape()
             ))
         }
     };
@@ -302,7 +321,12 @@ pub fn value_to_audio_channels(audio: &Value) -> Result<Vec<Vec<f64>>, String> {
     let interleaved: Vec<f64> = match audio {
         Value::Num(nums) => nums.data.iter().copied().collect(),
         Value::Byte(byte) => byte.data.iter().map(|&b| b as f64).collect(),
-        _ => return Err("Audio must be a numeric array".into()),
+        _ => {
+            return Err(format!(
+                "Audio must be a numeric array, but it is {}",
+                audio.type_name_plural()
+            ))
+        }
     };
     let (length, mut channels) = match audio.rank() {
         1 => (interleaved.len(), vec![interleaved]),
@@ -315,7 +339,8 @@ pub fn value_to_audio_channels(audio: &Value) -> Result<Vec<Vec<f64>>, String> {
         ),
         n => {
             return Err(format!(
-                "Audio must be a rank 1 or 2 numeric array, but it is rank {n}"
+                "Audio must be a rank 1 or 2 numeric array, but it is rank {n} with shape {}",
+                audio.shape()
             ))
         }
     };
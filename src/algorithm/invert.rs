@@ -624,7 +624,9 @@ pub(crate) fn under_instrs(
         &maybe_val!(store1copy!(Sys(SysOp::TlsConnect), Sys(SysOp::Close))),
         &maybe_val!(store1copy!(Sys(SysOp::TcpAccept), Sys(SysOp::Close))),
         &maybe_val!(store1copy!(Sys(SysOp::TcpListen), Sys(SysOp::Close))),
+        &maybe_val!(store1copy!(Sys(SysOp::Spinner), Sys(SysOp::Close))),
         &maybe_val!(store1copy!(Sys(SysOp::TlsListen), Sys(SysOp::Close))),
+        &maybe_val!(store1copy!(Sys(SysOp::WasmLoad), Sys(SysOp::Close))),
         &maybe_val!(stash1!(Sys(SysOp::FReadAllStr), Sys(SysOp::FWriteAll))),
         &maybe_val!(stash1!(Sys(SysOp::FReadAllBytes), Sys(SysOp::FWriteAll))),
         &maybe_val!(pat!(
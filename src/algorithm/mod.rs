@@ -27,6 +27,7 @@ mod monadic;
 pub mod permute;
 pub mod pervade;
 pub mod reduce;
+pub mod serialize;
 pub mod table;
 pub mod zip;
 
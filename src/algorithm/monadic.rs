@@ -1800,8 +1800,144 @@ impl Value {
     }
 }
 
+#[cfg(feature = "msgpack")]
+impl Value {
+    pub(crate) fn to_msgpack_bytes(&self, env: &Uiua) -> UiuaResult<Vec<u8>> {
+        let value = self.to_msgpack_value(env)?;
+        let mut bytes = Vec::new();
+        rmpv::encode::write_value(&mut bytes, &value).map_err(|e| env.error(e))?;
+        Ok(bytes)
+    }
+    /// Convert to MessagePack the same way as to JSON, except that
+    /// byte lists become binary data and map keys do not have to be strings
+    fn to_msgpack_value(&self, env: &Uiua) -> UiuaResult<rmpv::Value> {
+        Ok(match self {
+            Value::Num(n) if n.rank() == 0 => {
+                let n = n.data[0];
+                if n.fract() == 0.0 && n.abs() < i64::MAX as f64 {
+                    (n as i64).into()
+                } else {
+                    n.into()
+                }
+            }
+            Value::Byte(bytes) if bytes.rank() == 0 => {
+                let b = bytes.data[0];
+                if bytes.meta().flags.contains(ArrayFlags::BOOLEAN_LITERAL) {
+                    (b != 0).into()
+                } else {
+                    b.into()
+                }
+            }
+            Value::Byte(bytes)
+                if bytes.rank() == 1 && !bytes.is_map() && !bytes.meta().flags.is_boolean() =>
+            {
+                rmpv::Value::Binary(bytes.data.to_vec())
+            }
+            Value::Complex(_) => {
+                return Err(env.error("Cannot convert complex numbers to MessagePack"))
+            }
+            Value::Char(c) if c.rank() == 0 => c.data[0].to_string().into(),
+            Value::Char(c) if c.rank() == 1 => c.data.iter().collect::<String>().into(),
+            Value::Box(b) if b.rank() == 0 => b.data[0].0.to_msgpack_value(env)?,
+            value => {
+                if value.is_map() {
+                    rmpv::Value::Map(
+                        (value.map_kv().into_iter())
+                            .map(|(k, v)| Ok((k.to_msgpack_value(env)?, v.to_msgpack_value(env)?)))
+                            .collect::<UiuaResult<_>>()?,
+                    )
+                } else {
+                    rmpv::Value::Array(
+                        value
+                            .rows()
+                            .map(|row| row.to_msgpack_value(env))
+                            .collect::<Result<_, _>>()?,
+                    )
+                }
+            }
+        })
+    }
+    pub(crate) fn from_msgpack_bytes(bytes: &[u8], env: &Uiua) -> UiuaResult<Self> {
+        let mut rest = bytes;
+        let value = rmpv::decode::read_value(&mut rest).map_err(|e| env.error(e))?;
+        if !rest.is_empty() {
+            return Err(env.error(format!("MessagePack has {} trailing bytes", rest.len())));
+        }
+        Self::from_msgpack_value(value, env)
+    }
+    fn from_msgpack_value(value: rmpv::Value, env: &Uiua) -> UiuaResult<Self> {
+        // Values of different shapes or types are boxed, like in JSON
+        fn from_rows(rows: Vec<Value>) -> Value {
+            if rows.windows(2).all(|win| {
+                win[0].shape() == win[1].shape() && win[0].type_name() == win[1].type_name()
+            }) {
+                Value::from_row_values_infallible(rows)
+            } else {
+                Array::from(
+                    rows.into_iter()
+                        .map(Value::boxed_if_not)
+                        .collect::<EcoVec<_>>(),
+                )
+                .into()
+            }
+        }
+        let box_map = |value: Value| {
+            if value.map_keys().is_some() {
+                Boxed(value).into()
+            } else {
+                value
+            }
+        };
+        Ok(match value {
+            rmpv::Value::Nil => f64::NAN.into(),
+            rmpv::Value::Boolean(b) => b.into(),
+            rmpv::Value::Integer(i) => match i.as_u64() {
+                Some(n) if n < u8::MAX as u64 => (n as u8).into(),
+                _ => i.as_f64().unwrap_or(0.0).into(),
+            },
+            rmpv::Value::F32(n) => (n as f64).into(),
+            rmpv::Value::F64(n) => n.into(),
+            rmpv::Value::String(s) => match s.into_str() {
+                Some(s) => s.into(),
+                None => return Err(env.error("MessagePack string is not valid UTF-8")),
+            },
+            rmpv::Value::Binary(bytes) => Array::<u8>::from(bytes.as_slice()).into(),
+            rmpv::Value::Array(arr) => from_rows(
+                (arr.into_iter())
+                    .map(|v| Value::from_msgpack_value(v, env).map(box_map))
+                    .collect::<UiuaResult<_>>()?,
+            ),
+            rmpv::Value::Map(map) => {
+                let mut keys = Vec::with_capacity(map.len());
+                let mut values = Vec::with_capacity(map.len());
+                for (k, v) in map {
+                    let key = Value::from_msgpack_value(k, env)?;
+                    // String keys are boxed, like in JSON
+                    keys.push(if let Value::Char(_) = key {
+                        Boxed(key).into()
+                    } else {
+                        box_map(key)
+                    });
+                    values.push(box_map(Value::from_msgpack_value(v, env)?));
+                }
+                let mut values = from_rows(values);
+                values.map(from_rows(keys), env)?;
+                values
+            }
+            rmpv::Value::Ext(ty, _) => {
+                return Err(env.error(format!(
+                    "Cannot convert MessagePack extension type {ty} to an array"
+                )))
+            }
+        })
+    }
+}
+
 impl Value {
     pub(crate) fn to_csv(&self, env: &Uiua) -> UiuaResult<String> {
+        self.to_csv_with_delimiter(b',', env)
+    }
+    pub(crate) fn to_csv_with_delimiter(&self, _delimiter: u8, env: &Uiua) -> UiuaResult<String> {
         #[cfg(not(feature = "csv"))]
         return Err(env.error("CSV support is not enabled in this environment"));
         #[cfg(feature = "csv")]
@@ -1809,26 +1945,10 @@ impl Value {
             let mut buf = Vec::new();
             let mut writer = csv::WriterBuilder::new()
                 .flexible(true)
+                .delimiter(_delimiter)
                 .from_writer(&mut buf);
-            match self.rank() {
-                0 => writer
-                    .write_record([self.format()])
-                    .map_err(|e| env.error(e))?,
-                1 => {
-                    for row in self.rows() {
-                        writer
-                            .write_record(row.unboxed().rows().map(|v| v.format()))
-                            .map_err(|e| env.error(e))?;
-                    }
-                }
-                2 => {
-                    for row in self.rows() {
-                        writer
-                            .write_record(row.rows().map(|v| v.format()))
-                            .map_err(|e| env.error(e))?;
-                    }
-                }
-                n => return Err(env.error(format!("Cannot write a rank-{n} array to CSV"))),
+            for record in self.to_csv_records(env)? {
+                writer.write_record(record).map_err(|e| env.error(e))?;
             }
             writer.flush().map_err(|e| env.error(e))?;
             drop(writer);
@@ -1836,6 +1956,19 @@ impl Value {
             Ok(s)
         }
     }
+    /// Convert this array to the records of a CSV table
+    pub(crate) fn to_csv_records(&self, env: &Uiua) -> UiuaResult<Vec<Vec<String>>> {
+        Ok(match self.rank() {
+            0 => vec![vec![self.format()]],
+            1 => (self.rows())
+                .map(|row| row.unboxed().rows().map(|v| v.format()).collect())
+                .collect(),
+            2 => (self.rows())
+                .map(|row| row.rows().map(|v| v.format()).collect())
+                .collect(),
+            n => return Err(env.error(format!("Cannot write a rank-{n} array to CSV"))),
+        })
+    }
     pub(crate) fn to_xlsx(&self, env: &Uiua) -> UiuaResult<Vec<u8>> {
         #[cfg(not(feature = "simple_excel_writer"))]
         return Err(env.error("XLSX encoding is not enabled in this environment"));
@@ -1904,7 +2037,14 @@ impl Value {
                 .map_err(|e| env.error(e))
         }
     }
-    pub(crate) fn from_csv(_csv: &str, env: &mut Uiua) -> UiuaResult<Self> {
+    pub(crate) fn from_csv(csv: &str, env: &mut Uiua) -> UiuaResult<Self> {
+        Self::from_csv_with_delimiter(csv, b',', env)
+    }
+    pub(crate) fn from_csv_with_delimiter(
+        _csv: &str,
+        _delimiter: u8,
+        env: &mut Uiua,
+    ) -> UiuaResult<Self> {
         #[cfg(not(feature = "csv"))]
         return Err(env.error("CSV support is not enabled in this environment"));
         #[cfg(feature = "csv")]
@@ -1912,21 +2052,29 @@ impl Value {
             let mut reader = csv::ReaderBuilder::new()
                 .has_headers(false)
                 .flexible(true)
+                .delimiter(_delimiter)
                 .from_reader(_csv.as_bytes());
-            let fill = env.value_fill().cloned().unwrap_or_else(|| "".into());
-            env.with_fill(fill, |env| {
-                let mut rows = Vec::new();
-                for result in reader.records() {
-                    let record = result.map_err(|e| env.error(e))?;
-                    let mut row = EcoVec::new();
-                    for field in record.iter() {
-                        row.push(Boxed(field.into()));
-                    }
-                    rows.push(Array::new(row.len(), row));
-                }
-                Array::from_row_arrays(rows, env).map(Into::into)
-            })
-        }
+            let mut records = Vec::new();
+            for result in reader.records() {
+                let record = result.map_err(|e| env.error(e))?;
+                records.push(record.iter().map(Into::into).collect());
+            }
+            Self::from_csv_records(records, env)
+        }
+    }
+    /// Build a table of boxed strings from CSV records, filling short rows with empty strings
+    pub(crate) fn from_csv_records(records: Vec<Vec<String>>, env: &mut Uiua) -> UiuaResult<Self> {
+        let fill = env.value_fill().cloned().unwrap_or_else(|| "".into());
+        env.with_fill(fill, |env| {
+            let rows = records.into_iter().map(|record| {
+                let row: EcoVec<Boxed> = record
+                    .into_iter()
+                    .map(|field| Boxed(field.into()))
+                    .collect();
+                Array::new(row.len(), row)
+            });
+            Array::from_row_arrays(rows.collect::<Vec<_>>(), env).map(Into::into)
+        })
     }
     pub(crate) fn from_xlsx(_xlsx: &[u8], env: &mut Uiua) -> UiuaResult<Self> {
         #[cfg(not(feature = "calamine"))]
@@ -0,0 +1,226 @@
+//! Serialize Uiua values to/from a compact binary format
+//!
+//! The format starts with [`MAGIC`] and a version byte.
+//! Each value is then written as its type, its flags, its shape, its metadata, and its data.
+//! Integers other than the data are written as LEB128 varints.
+
+use ecow::EcoString;
+
+use crate::{
+    algorithm::IgnoreError, cowslice::CowSlice, Array, ArrayFlags, ArrayValue, Boxed, Complex,
+    Shape, Value,
+};
+
+/// The bytes that every serialized value starts with
+pub const MAGIC: &[u8; 4] = b"uiua";
+/// The current version of the format
+pub const VERSION: u8 = 1;
+
+const HAS_LABEL: u8 = 1;
+const HAS_MAP_KEYS: u8 = 2;
+
+/// The maximum number of arrays that may be nested in a serialized value
+///
+/// This keeps bad input from overflowing the stack.
+const MAX_DEPTH: usize = 1024;
+
+/// Serialize a value to bytes
+///
+/// Labels and map keys are preserved
+pub fn value_to_bytes(value: &Value) -> Vec<u8> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.push(VERSION);
+    write_value(value, &mut bytes);
+    bytes
+}
+
+/// Deserialize a value from bytes created with [`value_to_bytes`]
+pub fn value_from_bytes(bytes: &[u8]) -> Result<Value, String> {
+    let rest =
+        (bytes.strip_prefix(MAGIC.as_slice())).ok_or("Bytes are not a serialized Uiua value")?;
+    let (&version, rest) = rest
+        .split_first()
+        .ok_or("Serialized value is missing its version")?;
+    if version != VERSION {
+        return Err(format!(
+            "Serialized value has version {version}, \
+            but only version {VERSION} is supported"
+        ));
+    }
+    let mut reader = Reader(rest);
+    let value = reader.value(0)?;
+    if !reader.0.is_empty() {
+        return Err(format!(
+            "Serialized value has {} trailing bytes",
+            reader.0.len()
+        ));
+    }
+    Ok(value)
+}
+
+fn write_varint(mut n: usize, bytes: &mut Vec<u8>) {
+    while n >= 0x80 {
+        bytes.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    bytes.push(n as u8);
+}
+
+fn write_value(value: &Value, bytes: &mut Vec<u8>) {
+    match value {
+        Value::Byte(arr) => write_array(0, arr, bytes, |b, bytes| bytes.push(*b)),
+        Value::Num(arr) => write_array(1, arr, bytes, |n, bytes| bytes.extend(n.to_le_bytes())),
+        Value::Complex(arr) => write_array(2, arr, bytes, |c, bytes| {
+            bytes.extend(c.re.to_le_bytes());
+            bytes.extend(c.im.to_le_bytes());
+        }),
+        Value::Char(arr) => write_array(3, arr, bytes, |c, bytes| {
+            bytes.extend((*c as u32).to_le_bytes())
+        }),
+        Value::Box(arr) => write_array(4, arr, bytes, |b, bytes| write_value(&b.0, bytes)),
+    }
+}
+
+fn write_array<T: ArrayValue>(
+    tag: u8,
+    arr: &Array<T>,
+    bytes: &mut Vec<u8>,
+    write_elem: impl Fn(&T, &mut Vec<u8>),
+) {
+    let meta = arr.meta();
+    bytes.push(tag);
+    bytes.push(meta.flags.bits());
+    let mut has = 0;
+    if meta.label.is_some() {
+        has |= HAS_LABEL;
+    }
+    if meta.map_keys.is_some() {
+        has |= HAS_MAP_KEYS;
+    }
+    bytes.push(has);
+    write_varint(arr.shape.len(), bytes);
+    for &dim in &*arr.shape {
+        write_varint(dim, bytes);
+    }
+    if let Some(label) = &meta.label {
+        write_varint(label.len(), bytes);
+        bytes.extend(label.as_bytes());
+    }
+    if let Some(keys) = &meta.map_keys {
+        write_value(&keys.clone().normalized(), bytes);
+    }
+    for elem in &arr.data {
+        write_elem(elem, bytes);
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        if self.0.len() < n {
+            return Err("Serialized value ended unexpectedly".into());
+        }
+        let (taken, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(taken)
+    }
+    fn byte(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+    fn varint(&mut self) -> Result<usize, String> {
+        let mut n: usize = 0;
+        for shift in (0..usize::BITS).step_by(7) {
+            let byte = self.byte()?;
+            n |= ((byte & 0x7f) as usize)
+                .checked_shl(shift)
+                .ok_or("Serialized value has an invalid integer")?;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+        Err("Serialized value has an invalid integer".into())
+    }
+    fn value(&mut self, depth: usize) -> Result<Value, String> {
+        if depth >= MAX_DEPTH {
+            return Err("Serialized value is nested too deeply".into());
+        }
+        Ok(match self.byte()? {
+            0 => self.array(depth, 1, |r| r.byte())?.into(),
+            1 => self
+                .array(depth, 8, |r| {
+                    Ok(f64::from_le_bytes(r.take(8)?.try_into().unwrap()))
+                })?
+                .into(),
+            2 => self
+                .array(depth, 16, |r| {
+                    let re = f64::from_le_bytes(r.take(8)?.try_into().unwrap());
+                    let im = f64::from_le_bytes(r.take(8)?.try_into().unwrap());
+                    Ok(Complex::new(re, im))
+                })?
+                .into(),
+            3 => self
+                .array(depth, 4, |r| {
+                    let n = u32::from_le_bytes(r.take(4)?.try_into().unwrap());
+                    char::from_u32(n).ok_or_else(|| format!("{n:#x} is not a valid character"))
+                })?
+                .into(),
+            4 => self
+                .array(depth, 4, |r| r.value(depth + 1).map(Boxed))?
+                .into(),
+            tag => return Err(format!("Serialized value has invalid type tag {tag}")),
+        })
+    }
+    /// Read an array whose elements are each at least `min_size` bytes
+    ///
+    /// `depth` is the number of arrays the array is nested in
+    fn array<T: ArrayValue>(
+        &mut self,
+        depth: usize,
+        min_size: usize,
+        read_elem: impl Fn(&mut Self) -> Result<T, String>,
+    ) -> Result<Array<T>, String> {
+        let flags = ArrayFlags::from_bits_truncate(self.byte()?);
+        let has = self.byte()?;
+        let rank = self.varint()?;
+        if rank > self.0.len() {
+            return Err("Serialized value ended unexpectedly".into());
+        }
+        let shape: Shape = (0..rank)
+            .map(|_| self.varint())
+            .collect::<Result<Vec<_>, _>>()?
+            .into();
+        let label = if has & HAS_LABEL != 0 {
+            let len = self.varint()?;
+            let label = std::str::from_utf8(self.take(len)?)
+                .map_err(|_| "Serialized value has an invalid label")?;
+            Some(EcoString::from(label))
+        } else {
+            None
+        };
+        let keys = if has & HAS_MAP_KEYS != 0 {
+            Some(self.value(depth + 1)?)
+        } else {
+            None
+        };
+        // Check the size before allocating so that bad input cannot cause a huge allocation
+        let elems = (shape.iter())
+            .try_fold(1usize, |acc, &dim| acc.checked_mul(dim))
+            .filter(|&elems| elems.saturating_mul(min_size) <= self.0.len())
+            .ok_or("Serialized value ended unexpectedly")?;
+        let data = (0..elems)
+            .map(|_| read_elem(self))
+            .collect::<Result<CowSlice<T>, _>>()?;
+        let mut arr = Array::new(shape, data);
+        if let Some(keys) = keys {
+            (arr.map(keys, &IgnoreError)).map_err(|_| "Serialized value has invalid map keys")?;
+        }
+        if label.is_some() {
+            arr.meta_mut().label = label;
+        }
+        if !flags.is_empty() {
+            arr.meta_mut().flags = flags;
+        }
+        Ok(arr)
+    }
+}
@@ -884,6 +884,15 @@ code:
         if !target.exists() && target.extension().is_none() {
             target = target.with_extension("ua");
         }
+        // Directories are imported by their entry file
+        if target.is_dir() {
+            if let Some(entry) = (["lib.ua", "main.ua", "index.ua"].iter())
+                .map(|name| target.join(name))
+                .find(|path| path.is_file())
+            {
+                target = entry;
+            }
+        }
         let base = Path::new(".");
         if let (Ok(canon_target), Ok(canon_base)) = (target.canonicalize(), base.canonicalize()) {
             pathdiff::diff_paths(canon_target, canon_base).unwrap_or(target)
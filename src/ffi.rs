@@ -119,6 +119,15 @@ impl FromStr for FfiType {
             "unsigned int" => FfiType::UInt,
             "unsigned long" => FfiType::ULong,
             "unsigned long long" => FfiType::ULongLong,
+            "i16" => FfiType::Short,
+            "i32" => FfiType::Int,
+            "i64" => FfiType::LongLong,
+            "u8" => FfiType::UChar,
+            "u16" => FfiType::UShort,
+            "u32" => FfiType::UInt,
+            "u64" => FfiType::ULongLong,
+            "f32" => FfiType::Float,
+            "f64" => FfiType::Double,
             _ => return Err(format!("Unknown FFI type: {}", s)),
         })
     }
@@ -127,12 +127,27 @@ The `uiua` crate has the following noteable feature flags:
     - `image`: Enables image encoding and decoding
     - `gif`: Enables GIF encoding and decoding
     - `audio_encode`: Enables audio encoding and decoding
+    - `crypto`: Enables the `&hash`, `&sha`, `&fhash`, and Base58 system functions
+    - `gzip`: Enables the `&gzenc` and `&gzdec` system functions
+    - `qr`: Enables the `&qrenc` and `&qrdec` system functions
+    - `msgpack`: Enables the `&mpenc` and `&mpdec` system functions
+    - `jwt`: Enables the `&jwtver` system function
 - `native_sys`: Enables the [`NativeSys`] backend. This is the default backend used by the interpreter.
 - `audio`: Enables audio features in the [`NativeSys`] backend.
-- `https`: Enables the `&httpsw` system function
+- `tls`: Enables the `&httpsw` and `&httpgetall` system functions
 - `invoke`: Enables the `&invk` system function
 - `trash`: Enables the `&ftr` system function
+- `glob`: Enables the `&fglob` system function
+- `walkdir`: Makes the `&ftree` system function use `walkdir` in the [`NativeSys`] backend
+- `zip`: Enables the `&fzipr` and `&fzipw` system functions
+- `wasm_runtime`: Enables the `&wasml` and `&wasmc` system functions
+- `gpu`: Enables the `&gpu` system function
+- `spinner`: Enables the `&spin`, `&spinm`, and `&spind` system functions
 - `raw_mode`: Enables the `&raw` system function
+- `crossterm`: Makes the `&termclear` system function use `crossterm` instead of ANSI escape codes
+- `rpassword`: Makes the `&scanpwd` system function hide input in the [`NativeSys`] backend
+- `watch`: Enables the `&fwatch` system function
+- `notifications`: Makes the `&notify` system function show desktop notifications in the [`NativeSys`] backend
 */
 
 #![allow(
@@ -163,6 +178,7 @@ mod parse;
 mod primitive;
 #[doc(hidden)]
 pub mod profile;
+mod repl;
 mod run;
 mod shape;
 #[cfg(feature = "stand")]
@@ -171,12 +187,14 @@ pub mod stand;
 mod sys;
 #[cfg(feature = "native_sys")]
 mod sys_native;
+mod sys_recording;
+mod sys_sandbox;
 mod types;
 mod value;
 
 #[allow(unused_imports)]
 pub use self::{
-    algorithm::encode,
+    algorithm::{encode, serialize},
     array::*,
     assembly::*,
     boxed::*,
@@ -189,9 +207,12 @@ pub use self::{
     lsp::{spans, SpanKind},
     parse::{ident_modifier_args, parse, ParseError},
     primitive::*,
+    repl::*,
     run::*,
     shape::*,
     sys::*,
+    sys_recording::*,
+    sys_sandbox::*,
     value::*,
 };
 
@@ -299,6 +320,223 @@ mod tests {
         }
     }
 
+    #[test]
+    fn deserialize_nesting_limit() {
+        use super::*;
+        let nested = |depth: usize| {
+            let mut bytes = serialize::MAGIC.to_vec();
+            bytes.push(serialize::VERSION);
+            for _ in 0..depth {
+                bytes.extend([4, 0, 0, 0]);
+            }
+            bytes.extend([0, 0, 0, 0, 1]);
+            bytes
+        };
+        // Unoptimized builds use much more stack per level than the default test thread has
+        std::thread::Builder::new()
+            .stack_size(64 << 20)
+            .spawn(move || {
+                serialize::value_from_bytes(&nested(1000)).unwrap();
+                let Err(err) = serialize::value_from_bytes(&nested(100_000)) else {
+                    panic!("deeply nested value was deserialized");
+                };
+                assert!(err.contains("nested too deeply"), "{err}");
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn safe_sys_logs() {
+        use super::*;
+        let mut env = Uiua::with_backend(SafeSys::new());
+        env.run_str("&logd 1 &logw \"careful\" &loge \"oh no\"")
+            .unwrap();
+        let logs = env.downcast_backend::<SafeSys>().unwrap().take_logs();
+        assert_eq!(
+            logs,
+            [
+                (LogLevel::Error, "oh no".into()),
+                (LogLevel::Warn, "careful".into()),
+                (LogLevel::Debug, "1".into()),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "image")]
+    fn safe_sys_images() {
+        use super::*;
+        let mut env = Uiua::with_backend(SafeSys::new());
+        env.run_str("&ims $Gradient ⊞×. ÷⟜⇡50").unwrap();
+        let images = env.downcast_backend::<SafeSys>().unwrap().take_images();
+        assert_eq!(images.len(), 1);
+        assert_eq!((images[0].0.width(), images[0].0.height()), (50, 50));
+        assert_eq!(images[0].1.as_deref(), Some("Gradient"));
+    }
+
+    #[test]
+    fn safe_sys_virtual_io() {
+        use super::*;
+        let sys = SafeSys::new();
+        sys.add_file("in.txt", "hello");
+        sys.push_stdin_line("world");
+        let mut env = Uiua::with_backend(sys);
+        env.run_str(r#"&fwa "out.txt" ⊂⊂ &fras "in.txt" " " &sc"#)
+            .unwrap();
+        env.run_str(r#"&p &fe "out.txt""#).unwrap();
+        let sys = env.downcast_backend::<SafeSys>().unwrap();
+        assert_eq!(sys.file("out.txt").unwrap(), b"hello world");
+        assert_eq!(sys.take_stdout(), b"1\n");
+        assert!(env.run_str(r#"&fras "missing.txt""#).is_err());
+    }
+
+    #[test]
+    fn recording_sys() {
+        use super::*;
+        let mut env = Uiua::with_backend(RecordingSys::new(SafeSys::new()));
+        env.run_str(r#"&fwa "out.txt" "hi""#).unwrap();
+        assert!(env.run_str(r#"&fras "missing.txt""#).is_err());
+        let sys = env.downcast_backend::<RecordingSys<SafeSys>>().unwrap();
+        let calls = sys.take_calls();
+        let write = calls.iter().find(|c| c.method == "file_write_all").unwrap();
+        assert_eq!(write.args, ["out.txt", "2 bytes"]);
+        assert_eq!(write.result, Ok("()".into()));
+        let read = calls.iter().find(|c| c.method == "file_read_all").unwrap();
+        assert!(read.result.is_err());
+        assert!(sys.calls().is_empty());
+        assert_eq!(sys.inner().file("out.txt").unwrap(), b"hi");
+    }
+
+    #[test]
+    #[cfg(all(feature = "native_sys", unix))]
+    fn sandbox_sys_escapes() {
+        use super::*;
+        let root = std::env::temp_dir().join(format!("uiua-sandbox-{}", std::process::id()));
+        let allowed = root.join("allowed");
+        std::fs::create_dir_all(&allowed).unwrap();
+        std::fs::write(allowed.join("ok.txt"), "ok").unwrap();
+        std::fs::write(root.join("secret.txt"), "secret").unwrap();
+        _ = std::os::unix::fs::symlink(&root, allowed.join("link"));
+        let policy = SandboxPolicy::builder().allow_write(&allowed).build();
+        let mut env = Uiua::with_backend(SandboxSys::new(NativeSys, policy));
+        let dir = allowed.display();
+        env.run_str(&format!(r#"&fras "{dir}/ok.txt""#)).unwrap();
+        env.run_str(&format!(r#"&fwa "{dir}/./new.txt" "hi""#))
+            .unwrap();
+        for path in [
+            "../secret.txt",
+            "link/secret.txt",
+            "missing/../../secret.txt",
+        ] {
+            let res = env.run_str(&format!(r#"&fras "{dir}/{path}""#));
+            assert!(res.is_err(), "{path} was readable");
+        }
+        env.run_str(&format!(r#"&fglob "{dir}/*/*.txt""#)).unwrap();
+        assert_eq!(env.pop("paths").unwrap().row_count(), 0, "glob escaped");
+        assert!(env.run_str(r#"&var "PATH""#).is_err(), "PATH was readable");
+        for code in [
+            "&exit 3",
+            "&clget",
+            r#"&clset "hi""#,
+            "&camcap 0",
+            r#"&notify "title" "body""#,
+            "&wasml [0 97 115 109 1 0 0 0]",
+            r#"&wasmc "f" 0 []"#,
+            r#"&gpu "sin" [1 2 3]"#,
+            "&tcpa 0",
+        ] {
+            let Err(err) = env.run_str(&format!("# Experimental!\n{code}")) else {
+                panic!("{code} was allowed");
+            };
+            assert!(
+                err.to_string().contains("not permitted by sandbox policy"),
+                "{code} failed with {err}"
+            );
+        }
+        _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    #[cfg(feature = "native_sys")]
+    fn directory_import() {
+        use super::*;
+        let root = std::env::temp_dir().join(format!("uiua-package-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("lib.ua"), "U ~ \"util\"\nAnswer ← U~Answer").unwrap();
+        std::fs::write(root.join("util.ua"), "Answer ← 42").unwrap();
+        let mut env = Uiua::with_native_sys();
+        let res = env.run_str(&format!("~ \"{}\" ~ Answer\nAnswer", root.display()));
+        _ = std::fs::remove_dir_all(&root);
+        res.unwrap();
+        assert_eq!(env.pop_num().unwrap(), 42.0);
+    }
+
+    #[test]
+    #[cfg(feature = "wasm_runtime")]
+    fn wasm_execution_limit() {
+        use super::*;
+        // (module (func (export "spin") (loop (br 0))))
+        let spin = "[0 97 115 109 1 0 0 0 1 4 1 96 0 0 3 2 1 0 7 8 1 4 115 112 105 110 0 0 \
+                    10 9 1 7 0 3 64 12 0 11 11]";
+        let mut env =
+            Uiua::with_native_sys().with_execution_limit(std::time::Duration::from_millis(200));
+        let res = env.run_str(&format!(
+            "# Experimental!\n&wasmc \"spin\" &wasml {spin} []"
+        ));
+        let Err(err) = res else {
+            panic!("infinite loop was not stopped");
+        };
+        assert!(matches!(err.kind, UiuaErrorKind::Timeout(..)), "{err}");
+    }
+
+    #[test]
+    fn recursion_limit() {
+        use super::*;
+        let code = "F ← |1 ⨬(F -1|∘) =0.\nF";
+        let mut env = Uiua::with_safe_sys().with_recursion_limit(50);
+        env.push(100);
+        let Err(err) = env.run_str(code) else {
+            panic!("recursion limit was not reached");
+        };
+        assert!(err.to_string().contains("Recursion limit of 50 reached"));
+        env.push(10);
+        env.run_str(code).unwrap();
+        assert_eq!(env.pop_num().unwrap(), 0.0);
+    }
+
+    #[test]
+    fn stack_len() {
+        use super::*;
+        let mut env = Uiua::with_safe_sys();
+        env.run_str("&stacklen 1 2 3").unwrap();
+        assert_eq!(env.pop_num().unwrap(), 3.0);
+        // The stack height must not be baked in at compile time, even when pre-evaluating for the LSP
+        let mut comp = Compiler::new();
+        comp.pre_eval_mode(PreEvalMode::Lsp);
+        comp.load_str("1 2\nF ← &stacklen\nF").unwrap();
+        let mut env = Uiua::with_safe_sys();
+        env.run_compiler(&mut comp).unwrap();
+        assert_eq!(env.pop_num().unwrap(), 2.0);
+    }
+
+    #[test]
+    fn default_uuid() {
+        use super::*;
+        let uuid = SafeSys::new().gen_uuid();
+        let groups: Vec<&str> = uuid.split('-').collect();
+        assert_eq!(
+            groups.iter().map(|g| g.len()).collect::<Vec<_>>(),
+            [8, 4, 4, 4, 12]
+        );
+        assert!(uuid
+            .chars()
+            .all(|c| c == '-' || c.is_ascii_digit() || c.is_ascii_lowercase()));
+        assert!(groups[2].starts_with('4'));
+        assert!("89ab".contains(&groups[3][..1]));
+    }
+
     #[test]
     fn lsp_spans() {
         use super::*;
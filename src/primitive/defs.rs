@@ -1308,6 +1308,9 @@ primitive!(
     /// ex: ⬚π↙ 7 [8 3 9 2 0]
     /// This works with negative values as well.
     /// ex: ⬚π↙ ¯7 [8 3 9 2 0]
+    /// Filling with a character pads a string to a width. Negative amounts pad on the left.
+    /// ex: ⬚@ ↙ 8 "abc"
+    /// ex: ⬚@0↙ ¯5 "42"
     ///
     /// [infinity] can be used to take every row along an axis.
     /// ex: ↯2_3_4⇡24
@@ -2470,6 +2473,10 @@ primitive!(
     /// While [spawn]'s function will be called immediately, [pool]'s function will be called when a thread in the pool is available.
     /// The thread pool has as many threads as the machine has processors.
     /// If all threads in the pool are busy, then [pool] will block until a thread is available.
+    ///
+    /// Independent computations can be run in parallel by [pool]ing each of them and [wait]ing on all of the thread ids.
+    /// The results are pushed in the same order as the thread ids.
+    /// ex: wait [pool(/+⇡1e5) pool(/×+1⇡10) pool(⧻⇌⇡20)]
     ([1], Pool, Thread, "pool", Impure),
     /// Wait for a thread to finish and push its results to the stack
     ///
@@ -3087,6 +3094,12 @@ primitive!(
     /// A length 3 last axis is an RGB image.
     /// A length 4 last axis is an RGB image with an alpha channel.
     ///
+    /// Some tools instead put the channels on the first axis.
+    /// Such images can be converted to the expected layout with [transpose], and back with [un][transpose].
+    /// ex: ↯3_2_2 0.5 # Channels first
+    ///   : ⍉          # Channels last
+    ///   : °⍉         # Channels first again
+    ///
     /// You can decode a byte array into an image with [un][img].
     ///
     /// Supported formats are `jpg`, `png`, `bmp`, `gif`, `ico`, and `qoi`.
@@ -423,7 +423,7 @@ impl Primitive {
                 | (Tuples | Choose | Permute)
                 | Struct
                 | (Last | Sort | Chunks | Coordinate | Astar | Fft | Triangle | Case | Layout)
-                | Sys(Ffi | MemCopy | MemFree | TlsListen)
+                | Sys(Ffi | MemCopy | MemFree | TlsListen | WasmLoad | WasmCall | GpuMap)
                 | (Stringify | Quote | Sig)
         )
     }
@@ -1250,41 +1250,45 @@ impl ImplPrimitive {
     }
 }
 
-fn regex(env: &mut Uiua) -> UiuaResult {
+/// Compile a regex pattern, reusing previously compiled patterns
+pub(crate) fn cached_regex(pattern: &str, env: &Uiua) -> UiuaResult<Regex> {
     thread_local! {
         pub static REGEX_CACHE: RefCell<HashMap<String, Regex>> = RefCell::new(HashMap::new());
     }
+    REGEX_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if let Some(regex) = cache.get(pattern) {
+            return Ok(regex.clone());
+        }
+        let regex =
+            Regex::new(pattern).map_err(|e| env.error(format!("Invalid pattern: {}", e)))?;
+        cache.insert(pattern.into(), regex.clone());
+        Ok(regex)
+    })
+}
+
+fn regex(env: &mut Uiua) -> UiuaResult {
     let pattern = env.pop(1)?.as_string(env, "Pattern must be a string")?;
     let target = env
         .pop(1)?
         .as_string(env, "Matching target must be a string")?;
-    REGEX_CACHE.with(|cache| -> UiuaResult {
-        let mut cache = cache.borrow_mut();
-        let regex = if let Some(regex) = cache.get(&pattern) {
-            regex
-        } else {
-            let regex =
-                Regex::new(&pattern).map_err(|e| env.error(format!("Invalid pattern: {}", e)))?;
-            cache.entry(pattern.clone()).or_insert(regex.clone())
-        };
+    let regex = cached_regex(&pattern, env)?;
 
-        let mut matches: Value =
-            Array::<Boxed>::new([0, regex.captures_len()].as_slice(), []).into();
+    let mut matches: Value = Array::<Boxed>::new([0, regex.captures_len()].as_slice(), []).into();
 
-        for caps in regex.captures_iter(&target) {
-            let row: EcoVec<Boxed> = caps
-                .iter()
-                .flat_map(|m| {
-                    m.map(|m| Boxed(Value::from(m.as_str())))
-                        .or_else(|| env.value_fill().cloned().map(Value::boxed_if_not))
-                })
-                .collect();
-            matches.append(row.into(), false, env)?;
-        }
+    for caps in regex.captures_iter(&target) {
+        let row: EcoVec<Boxed> = caps
+            .iter()
+            .flat_map(|m| {
+                m.map(|m| Boxed(Value::from(m.as_str())))
+                    .or_else(|| env.value_fill().cloned().map(Value::boxed_if_not))
+            })
+            .collect();
+        matches.append(row.into(), false, env)?;
+    }
 
-        env.push(matches);
-        Ok(())
-    })
+    env.push(matches);
+    Ok(())
 }
 
 thread_local! {
@@ -1778,8 +1782,35 @@ mod tests {
             for line in &prim.doc().lines {
                 if let PrimDocLine::Example(ex) = line {
                     if [
-                        "&sl", "&tcpc", "&tlsc", "&ast", "&clset", "&fo", "&fc", "&fde", "&ftr",
-                        "&fld", "&fif", "&fras", "&frab", "&fmd",
+                        "&sl",
+                        "&tcpc",
+                        "&tlsc",
+                        "&ast",
+                        "&clset",
+                        "&fo",
+                        "&fc",
+                        "&ftr",
+                        "&fld",
+                        "&frab",
+                        "&fmd",
+                        "&frmd",
+                        "&fglob",
+                        "&sh",
+                        "&fhash",
+                        "&fcan",
+                        "&fabs",
+                        "&ftmpf",
+                        "&ftmpd",
+                        "&fwatch",
+                        "&fzipr",
+                        "&fzipw",
+                        "&httpgetall",
+                        "&measurens",
+                        "&cpucount",
+                        "&cwd",
+                        "&ftree",
+                        "&frcsv",
+                        "&fwcsv",
                     ]
                     .iter()
                     .any(|prim| ex.input.contains(prim))
@@ -1787,7 +1818,10 @@ mod tests {
                         continue;
                     }
                     println!("{prim} example:\n{}", ex.input); // Allow println
-                    let mut env = Uiua::with_safe_sys();
+                    let sys = SafeSys::new();
+                    sys.add_file("example.txt", "Hello, World!");
+                    sys.add_file("data.jsonl", "[1, 2]\n[3, 4]\n");
+                    let mut env = Uiua::with_backend(sys);
                     match env.run_str(&ex.input) {
                         Ok(mut comp) => {
                             if let Some(diag) = comp.take_diagnostics().into_iter().next() {
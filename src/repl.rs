@@ -0,0 +1,76 @@
+//! Line-by-line evaluation of Uiua code
+
+use crate::{Compiler, PreEvalMode, Uiua, Value};
+
+/// A Uiua REPL that evaluates code one line at a time
+///
+/// Bindings and the stack persist between lines.
+/// If a line fails to compile or run, the REPL is restored to its state before the line.
+///
+/// ```rust
+/// use uiua::*;
+///
+/// let mut repl = UiuaRepl::new(Uiua::with_safe_sys());
+/// assert_eq!(repl.eval_line("X ← 5").unwrap(), Vec::<String>::new());
+/// assert_eq!(repl.eval_line("+1 X").unwrap(), ["6"]);
+/// assert_eq!(repl.eval_line(".").unwrap(), ["6"]);
+/// assert!(repl.eval_line("+1 Y").is_err());
+/// assert_eq!(repl.stack().len(), 2);
+/// ```
+pub struct UiuaRepl {
+    env: Uiua,
+    compiler: Compiler,
+}
+
+impl Default for UiuaRepl {
+    fn default() -> Self {
+        Self::new(Uiua::with_safe_sys())
+    }
+}
+
+impl UiuaRepl {
+    /// Create a new REPL that runs code in the given runtime
+    ///
+    /// The runtime's system backend is also used for compile-time code.
+    pub fn new(env: Uiua) -> Self {
+        let mut compiler = Compiler::with_backend(env.rt.backend.clone());
+        compiler.pre_eval_mode(PreEvalMode::Line);
+        Self { env, compiler }
+    }
+    /// Evaluate a line of code
+    ///
+    /// On success, returns the formatted representations of the values that the line added to the stack, from bottom to top.
+    /// On failure, returns the error report.
+    pub fn eval_line(&mut self, line: &str) -> Result<Vec<String>, String> {
+        let backup_comp = self.compiler.clone();
+        let backup_stack = self.env.stack().to_vec();
+        let res = (self.compiler.load_str(line).map(drop))
+            .and_then(|()| self.env.run_compiler(&mut self.compiler));
+        if let Err(e) = res {
+            self.compiler = backup_comp;
+            self.env.rt.stack = backup_stack;
+            return Err(e.to_string());
+        }
+        let stack = self.env.stack();
+        let unchanged = (backup_stack.iter().zip(stack))
+            .take_while(|(a, b)| a == b)
+            .count();
+        Ok(stack[unchanged..].iter().map(Value::show).collect())
+    }
+    /// Get the current stack
+    pub fn stack(&self) -> &[Value] {
+        self.env.stack()
+    }
+    /// Clear the stack, returning the values that were on it
+    pub fn take_stack(&mut self) -> Vec<Value> {
+        self.env.take_stack()
+    }
+    /// Get a reference to the underlying runtime
+    pub fn env(&self) -> &Uiua {
+        &self.env
+    }
+    /// Get a mutable reference to the underlying runtime
+    pub fn env_mut(&mut self) -> &mut Uiua {
+        &mut self.env
+    }
+}
@@ -16,6 +16,7 @@ use std::{
 
 use crossbeam_channel::{Receiver, Sender, TryRecvError};
 use enum_iterator::{all, Sequence};
+use parking_lot::Mutex;
 use thread_local::ThreadLocal;
 
 use crate::{
@@ -26,9 +27,9 @@ use crate::{
     function::*,
     lex::Span,
     value::Value,
-    Assembly, BindingKind, CodeSpan, Compiler, Complex, Ident, Inputs, IntoSysBackend, LocalName,
-    Primitive, SafeSys, SysBackend, SysOp, TraceFrame, UiuaError, UiuaErrorKind, UiuaResult,
-    VERSION,
+    Assembly, BindingKind, CodeSpan, Compiler, Complex, Ident, Inputs, InterruptFn, IntoSysBackend,
+    LocalName, Primitive, SafeSys, SysBackend, SysOp, TraceFrame, UiuaError, UiuaErrorKind,
+    UiuaResult, VERSION,
 };
 
 /// The Uiua interpreter
@@ -60,6 +61,8 @@ pub(crate) struct Runtime {
     fill_boundary_stack: Vec<usize>,
     /// A limit on the execution duration in milliseconds
     pub(crate) execution_limit: Option<f64>,
+    /// A limit on the depth of the call stack
+    recursion_limit: usize,
     /// The time at which execution started
     pub(crate) execution_start: f64,
     /// Whether the program was interrupted
@@ -80,8 +83,12 @@ pub(crate) struct Runtime {
     pub(crate) output_comments: HashMap<usize, Vec<Vec<Value>>>,
     /// Memoized values
     pub(crate) memo: Arc<ThreadLocal<RefCell<MemoMap>>>,
+    /// Values stored with `&cset`
+    pub(crate) cache: Arc<Mutex<HashMap<String, Value>>>,
 }
 
+const DEFAULT_RECURSION_LIMIT: usize = 1000;
+
 type MemoMap = HashMap<FunctionId, HashMap<Vec<Value>, Vec<Value>>>;
 
 impl AsRef<Assembly> for Uiua {
@@ -200,11 +207,13 @@ impl Default for Runtime {
             cli_arguments: Vec::new(),
             cli_file_path: PathBuf::new(),
             execution_limit: None,
+            recursion_limit: DEFAULT_RECURSION_LIMIT,
             execution_start: 0.0,
             interrupted: None,
             thread: ThisThread::default(),
             output_comments: HashMap::new(),
             memo: Arc::new(ThreadLocal::new()),
+            cache: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -268,6 +277,20 @@ impl Uiua {
         self.rt.execution_limit = limit.map(|limit| limit.as_secs_f64());
         self
     }
+    /// Limit the depth of nested function calls
+    ///
+    /// Exceeding the limit is an error rather than a stack overflow.
+    /// The default limit is 1000.
+    pub fn with_recursion_limit(mut self, limit: usize) -> Self {
+        self.set_recursion_limit(limit);
+        self
+    }
+    /// Limit the depth of nested function calls
+    ///
+    /// See [`Uiua::with_recursion_limit`].
+    pub fn set_recursion_limit(&mut self, limit: usize) {
+        self.rt.recursion_limit = limit;
+    }
     /// Set the interrupted hook
     pub fn with_interrupt_hook(mut self, hook: impl Fn() -> bool + Send + Sync + 'static) -> Self {
         self.rt.interrupted = Some(Arc::new(hook));
@@ -344,6 +367,7 @@ impl Uiua {
                 env.rt = Runtime {
                     backend: env.rt.backend.clone(),
                     execution_limit: env.rt.execution_limit,
+                    recursion_limit: env.rt.recursion_limit,
                     time_instrs: env.rt.time_instrs,
                     output_comments: env.rt.output_comments.clone(),
                     ..Runtime::default()
@@ -394,6 +418,12 @@ code:
         }
     }
     fn exec(&mut self, frame: StackFrame) -> UiuaResult {
+        if self.rt.call_stack.len() > self.rt.recursion_limit {
+            return Err(self.error(format!(
+                "Recursion limit of {} reached",
+                self.rt.recursion_limit
+            )));
+        }
         let slice = frame.slice;
         self.rt.call_stack.push(frame);
         let mut formatted_instr = String::new();
@@ -740,6 +770,19 @@ code:
         }
         Ok(())
     }
+    /// Get a function that checks whether execution should stop
+    ///
+    /// This is for backend calls that run for a long time without returning to the interpreter.
+    pub(crate) fn interrupt_fn(&self) -> InterruptFn {
+        let backend = self.rt.backend.clone();
+        let start = self.rt.execution_start;
+        let limit = self.rt.execution_limit;
+        let hook = self.rt.interrupted.clone();
+        Arc::new(move || {
+            limit.is_some_and(|limit| backend.now() - start > limit)
+                || hook.as_ref().is_some_and(|hook| hook())
+        })
+    }
     pub(crate) fn with_span<T>(
         &mut self,
         span: usize,
@@ -1397,30 +1440,9 @@ code:
         self.respect_execution_limit()?;
         self.call_frame(frame)
     }
-    /// Spawn a thread
-    pub(crate) fn spawn(
-        &mut self,
-        capture_count: usize,
-        _pool: bool,
-        f: impl FnOnce(&mut Self) -> UiuaResult + Send + 'static,
-    ) -> UiuaResult {
-        if self.rt.stack.len() < capture_count {
-            return Err(self.error(format!(
-                "Expected at least {} value(s) on the stack, but there are {}",
-                capture_count,
-                self.rt.stack.len()
-            )))?;
-        }
-        let (this_send, child_recv) = crossbeam_channel::unbounded();
-        let (child_send, this_recv) = crossbeam_channel::unbounded();
-        let thread = ThisThread {
-            parent: Some(Channel {
-                send: child_send,
-                recv: child_recv,
-            }),
-            ..ThisThread::default()
-        };
-        let mut env = Uiua {
+    /// Create a runtime for a new thread, moving the top `capture_count` values of the stack to it
+    fn thread_env(&mut self, capture_count: usize, thread: ThisThread) -> Self {
+        Uiua {
             asm: self.asm.clone(),
             rt: Runtime {
                 stack: (self.rt.stack)
@@ -1439,13 +1461,98 @@ code:
                 cli_file_path: self.rt.cli_file_path.clone(),
                 backend: self.rt.backend.clone(),
                 execution_limit: self.rt.execution_limit,
+                recursion_limit: self.rt.recursion_limit,
                 execution_start: self.rt.execution_start,
                 interrupted: self.rt.interrupted.clone(),
                 output_comments: HashMap::new(),
                 memo: self.rt.memo.clone(),
+                cache: self.rt.cache.clone(),
                 thread,
             },
+        }
+    }
+    /// Call a function in a new thread, waiting at most `seconds` for it to finish
+    ///
+    /// If the function finishes in time, returns the values it left on the stack.
+    /// If it does not, it is interrupted and `None` is returned.
+    pub(crate) fn call_with_timeout(
+        &mut self,
+        f: Function,
+        seconds: f64,
+    ) -> UiuaResult<Option<Vec<Value>>> {
+        let capture_count = f.signature().args;
+        if self.rt.stack.len() < capture_count {
+            return Err(self.error(format!(
+                "Expected at least {} value(s) on the stack, but there are {}",
+                capture_count,
+                self.rt.stack.len()
+            )))?;
+        }
+        let mut env = self.thread_env(capture_count, ThisThread::default());
+        // Without threads, the execution limit is used to stop the function instead
+        #[cfg(target_arch = "wasm32")]
+        {
+            let now = env.rt.backend.now();
+            let remaining =
+                (env.rt.execution_limit).map(|limit| limit - (now - env.rt.execution_start));
+            let limited = remaining.map_or(true, |remaining| seconds < remaining);
+            if limited {
+                env.rt.execution_start = now;
+                env.rt.execution_limit = Some(seconds.max(0.0));
+            }
+            match env.call(f) {
+                Ok(()) => Ok(Some(env.take_stack())),
+                Err(e) if limited && matches!(e.kind, UiuaErrorKind::Timeout(..)) => Ok(None),
+                Err(e) => Err(e),
+            }
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            use std::sync::atomic::{AtomicBool, Ordering};
+            let cancelled = Arc::new(AtomicBool::new(false));
+            let parent_hook = env.rt.interrupted.take();
+            let hook_cancelled = cancelled.clone();
+            env.rt.interrupted = Some(Arc::new(move || {
+                hook_cancelled.load(Ordering::Relaxed) || parent_hook.as_ref().is_some_and(|h| h())
+            }));
+            let (send, recv) = crossbeam_channel::bounded(1);
+            std::thread::Builder::new()
+                .spawn(move || _ = send.send(env.call(f).map(|_| env.take_stack())))
+                .map_err(|e| self.error(format!("Error spawning thread: {e}")))?;
+            let timeout = Duration::try_from_secs_f64(seconds.max(0.0)).unwrap_or(Duration::MAX);
+            match recv.recv_timeout(timeout) {
+                Ok(res) => res.map(Some),
+                Err(_) => {
+                    cancelled.store(true, Ordering::Relaxed);
+                    Ok(None)
+                }
+            }
+        }
+    }
+    /// Spawn a thread
+    pub(crate) fn spawn(
+        &mut self,
+        capture_count: usize,
+        _pool: bool,
+        f: impl FnOnce(&mut Self) -> UiuaResult + Send + 'static,
+    ) -> UiuaResult {
+        if self.rt.stack.len() < capture_count {
+            return Err(self.error(format!(
+                "Expected at least {} value(s) on the stack, but there are {}",
+                capture_count,
+                self.rt.stack.len()
+            )))?;
+        }
+        let (this_send, child_recv) = crossbeam_channel::unbounded();
+        let (child_send, this_recv) = crossbeam_channel::unbounded();
+        let thread = ThisThread {
+            parent: Some(Channel {
+                send: child_send,
+                recv: child_recv,
+            }),
+            ..ThisThread::default()
         };
+        let mut env = self.thread_env(capture_count, thread);
         #[cfg(not(target_arch = "wasm32"))]
         let recv = {
             let (send, recv) = crossbeam_channel::unbounded();
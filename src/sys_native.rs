@@ -5,17 +5,17 @@ use std::{
     io::{stderr, stdin, stdout, BufReader, Read, Write},
     net::*,
     path::{Path, PathBuf},
-    process::{Child, ChildStderr, ChildStdin, ChildStdout, Command, Stdio},
+    process::{Child, ChildStderr, ChildStdin, ChildStdout, Command, ExitStatus, Output, Stdio},
     slice,
     sync::{
         atomic::{self, AtomicBool, AtomicU64},
         Arc,
     },
     thread::sleep,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use crate::{terminal_size, GitTarget, Handle, SysBackend};
+use crate::{terminal_size, GitTarget, Handle, InterruptFn, SysBackend};
 use dashmap::DashMap;
 use once_cell::sync::Lazy;
 
@@ -47,6 +47,10 @@ struct GlobalNativeSys {
     ffi: crate::FfiState,
     #[cfg(all(feature = "gif", feature = "invoke"))]
     gifs_child: parking_lot::Mutex<Option<Child>>,
+    #[cfg(feature = "wasm_runtime")]
+    wasm_instances: DashMap<Handle, Arc<parking_lot::Mutex<WasmInstance>>>,
+    #[cfg(feature = "spinner")]
+    spinners: DashMap<Handle, indicatif::ProgressBar>,
 }
 
 enum SysStream<'a> {
@@ -190,6 +194,71 @@ impl Write for &TlsSocket {
     }
 }
 
+#[cfg(feature = "wasm_runtime")]
+struct WasmInstance {
+    store: wasmtime::Store<crate::InterruptFn>,
+    instance: wasmtime::Instance,
+}
+
+#[cfg(feature = "wasm_runtime")]
+static WASM_ENGINE: Lazy<wasmtime::Engine> = Lazy::new(|| {
+    let mut config = wasmtime::Config::new();
+    config.epoch_interruption(true);
+    wasmtime::Engine::new(&config).expect("WebAssembly engine config is valid")
+});
+
+/// The number of WebAssembly calls that are currently running
+#[cfg(feature = "wasm_runtime")]
+static WASM_RUNNING: atomic::AtomicUsize = atomic::AtomicUsize::new(0);
+
+/// Marks WebAssembly code as running for as long as it is alive
+///
+/// While any code is running, a thread advances the engine's epoch
+/// so that the code regularly checks whether it should stop.
+#[cfg(feature = "wasm_runtime")]
+struct WasmRunning;
+
+#[cfg(feature = "wasm_runtime")]
+impl WasmRunning {
+    fn start() -> Self {
+        if WASM_RUNNING.fetch_add(1, atomic::Ordering::SeqCst) == 0 {
+            _ = std::thread::Builder::new()
+                .name("wasm epoch".into())
+                .spawn(|| {
+                    while WASM_RUNNING.load(atomic::Ordering::SeqCst) > 0 {
+                        sleep(Duration::from_millis(10));
+                        WASM_ENGINE.increment_epoch();
+                    }
+                });
+        }
+        WasmRunning
+    }
+}
+
+#[cfg(feature = "wasm_runtime")]
+impl Drop for WasmRunning {
+    fn drop(&mut self) {
+        WASM_RUNNING.fetch_sub(1, atomic::Ordering::SeqCst);
+    }
+}
+
+/// Create a store whose code stops with an error once `interrupted` returns `true`
+#[cfg(feature = "wasm_runtime")]
+fn wasm_store(interrupted: crate::InterruptFn) -> wasmtime::Store<crate::InterruptFn> {
+    let mut store = wasmtime::Store::new(&WASM_ENGINE, interrupted);
+    store.set_epoch_deadline(1);
+    store.epoch_deadline_callback(|store| {
+        if (store.data())() {
+            Err(wasmtime::Error::msg(
+                "WebAssembly execution was interrupted",
+            ))
+        } else {
+            Ok(wasmtime::UpdateDeadline::Continue(1))
+        }
+    });
+    store
+}
+
 struct TlsListener {
     listener: TcpListener,
     #[cfg(feature = "tls")]
@@ -222,6 +291,10 @@ impl Default for GlobalNativeSys {
             ffi: Default::default(),
             #[cfg(all(feature = "gif", feature = "invoke"))]
             gifs_child: parking_lot::Mutex::new(None),
+            #[cfg(feature = "wasm_runtime")]
+            wasm_instances: DashMap::new(),
+            #[cfg(feature = "spinner")]
+            spinners: DashMap::new(),
         }
     }
 }
@@ -237,12 +310,30 @@ impl GlobalNativeSys {
                 && !self.tcp_listeners.contains_key(&handle)
                 && !self.tcp_sockets.contains_key(&handle)
                 && !self.tls_sockets.contains_key(&handle)
+                && !self.has_wasm_instance(handle)
+                && !self.has_spinner(handle)
             {
                 return handle;
             }
         }
         panic!("Ran out of file handles");
     }
+    fn has_wasm_instance(&self, _handle: Handle) -> bool {
+        #[cfg(feature = "wasm_runtime")]
+        {
+            self.wasm_instances.contains_key(&_handle)
+        }
+        #[cfg(not(feature = "wasm_runtime"))]
+        false
+    }
+    fn has_spinner(&self, _handle: Handle) -> bool {
+        #[cfg(feature = "spinner")]
+        {
+            self.spinners.contains_key(&_handle)
+        }
+        #[cfg(not(feature = "spinner"))]
+        false
+    }
     fn get_stream(&self, handle: Handle) -> Result<SysStream, String> {
         Ok(if let Some(file) = self.files.get_mut(&handle) {
             SysStream::File(file)
@@ -278,6 +369,10 @@ impl GlobalNativeSys {
 
 static NATIVE_SYS: Lazy<GlobalNativeSys> = Lazy::new(Default::default);
 
+thread_local! {
+    static MONOTONIC_ANCHOR: std::cell::Cell<Option<Instant>> = const { std::cell::Cell::new(None) };
+}
+
 #[cfg(all(feature = "audio", feature = "binary"))]
 #[doc(hidden)]
 pub fn set_audio_stream_time(time: f64) {
@@ -293,6 +388,122 @@ pub fn set_audio_stream_time_port(port: u16) -> std::io::Result<()> {
     Ok(())
 }
 
+/// Get a new unique path in the system's temporary directory
+fn temp_path() -> Result<PathBuf, String> {
+    let dir = env::temp_dir();
+    let dir = if dir.is_absolute() {
+        dir
+    } else {
+        env::current_dir().map_err(|e| e.to_string())?.join(dir)
+    };
+    Ok(dir.join(format!("uiua-{}", uuid::Uuid::new_v4())))
+}
+
+/// Wait for a child process to exit, killing it once `interrupted` returns `true`
+fn wait_interruptible(child: &mut Child, interrupted: &InterruptFn) -> Result<ExitStatus, String> {
+    loop {
+        if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+            return Ok(status);
+        }
+        if interrupted() {
+            _ = child.kill();
+            _ = child.wait();
+            return Err("Command was interrupted".into());
+        }
+        sleep(Duration::from_millis(10));
+    }
+}
+
+/// Run a command and capture its output, killing it once `interrupted` returns `true`
+fn output_interruptible(
+    command: &mut Command,
+    interrupted: &InterruptFn,
+) -> Result<Output, String> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    // The pipes are drained on their own threads so that a full pipe never blocks the child
+    let mut stdout = child.stdout.take().unwrap();
+    let mut stderr = child.stderr.take().unwrap();
+    let stdout = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        _ = stdout.read_to_end(&mut buf);
+        buf
+    });
+    let stderr = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        _ = stderr.read_to_end(&mut buf);
+        buf
+    });
+    let status = wait_interruptible(&mut child, interrupted)?;
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+/// How long HTTP GET requests wait to connect to a server
+#[cfg(feature = "tls")]
+const HTTP_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long HTTP GET requests wait for a server to send or accept data
+#[cfg(feature = "tls")]
+const HTTP_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A TCP stream whose reads fail once `interrupted` returns `true`
+/// or once no data has arrived for [`HTTP_IDLE_TIMEOUT`]
+#[cfg(feature = "tls")]
+struct InterruptibleStream {
+    stream: TcpStream,
+    interrupted: InterruptFn,
+}
+
+#[cfg(feature = "tls")]
+impl InterruptibleStream {
+    fn new(stream: TcpStream, interrupted: InterruptFn) -> std::io::Result<Self> {
+        stream.set_read_timeout(Some(Duration::from_millis(100)))?;
+        stream.set_write_timeout(Some(HTTP_IDLE_TIMEOUT))?;
+        Ok(InterruptibleStream {
+            stream,
+            interrupted,
+        })
+    }
+}
+
+#[cfg(feature = "tls")]
+impl Read for InterruptibleStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        use std::io::{Error, ErrorKind};
+        let start = Instant::now();
+        loop {
+            match self.stream.read(buf) {
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                    if (self.interrupted)() {
+                        return Err(Error::other("Request was interrupted"));
+                    }
+                    if start.elapsed() > HTTP_IDLE_TIMEOUT {
+                        return Err(Error::new(ErrorKind::TimedOut, "Request timed out"));
+                    }
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tls")]
+impl Write for InterruptibleStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.stream.write(buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.stream.flush()
+    }
+}
+
 pub(crate) fn output_enabled() -> bool {
     NATIVE_SYS.output_enabled.load(atomic::Ordering::Relaxed)
 }
@@ -357,6 +568,21 @@ impl SysBackend for NativeSys {
         }
         Ok(Some(String::from_utf8(buffer).map_err(|e| e.to_string())?))
     }
+    #[cfg(feature = "rpassword")]
+    fn scan_password(&self) -> Result<Option<String>, String> {
+        use std::io::IsTerminal;
+        if !output_enabled() {
+            return Ok(None);
+        }
+        if !stdin().is_terminal() {
+            return self.scan_line_stdin();
+        }
+        match rpassword::read_password() {
+            Ok(pwd) => Ok(Some(pwd)),
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
     fn scan_stdin(&self, count: usize) -> Result<Vec<u8>, String> {
         if !output_enabled() {
             return Ok(Vec::new());
@@ -368,6 +594,23 @@ impl SysBackend for NativeSys {
     fn save_error_color(&self, message: String, colored: String) {
         NATIVE_SYS.colored_errors.insert(message, colored);
     }
+    fn is_tty(&self) -> bool {
+        use std::io::IsTerminal;
+        output_enabled() && stdout().is_terminal()
+    }
+    fn cpu_count(&self) -> Result<usize, String> {
+        Ok(std::thread::available_parallelism().map_or(1, |n| n.get()))
+    }
+    fn resident_bytes(&self) -> u64 {
+        // The resident set size is read from procfs, so other platforms report 0
+        (fs::read_to_string("/proc/self/status").ok())
+            .and_then(|status| {
+                let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+                let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+                Some(kb * 1024)
+            })
+            .unwrap_or(0)
+    }
     fn term_size(&self) -> Result<(usize, usize), String> {
         let (w, h) = terminal_size().ok_or("Failed to get terminal size")?;
         Ok((w, h.saturating_sub(1)))
@@ -387,17 +630,95 @@ impl SysBackend for NativeSys {
         }
         Ok(())
     }
+    fn clear_screen(&self) -> Result<(), String> {
+        if !output_enabled() {
+            return Ok(());
+        }
+        let mut stdout = stdout().lock();
+        #[cfg(feature = "crossterm")]
+        crossterm::execute!(
+            stdout,
+            crossterm::terminal::Clear(crossterm::terminal::ClearType::All),
+            crossterm::cursor::MoveTo(0, 0)
+        )
+        .map_err(|e| e.to_string())?;
+        #[cfg(not(feature = "crossterm"))]
+        stdout
+            .write_all(b"\x1b[2J\x1b[H")
+            .map_err(|e| e.to_string())?;
+        stdout.flush().map_err(|e| e.to_string())
+    }
     fn var(&self, name: &str) -> Option<String> {
         env::var(name).ok()
     }
     fn file_exists(&self, path: &str) -> bool {
         fs::metadata(path).is_ok()
     }
+    fn canonicalize(&self, path: &str) -> Result<String, String> {
+        if fs::metadata(path).is_err() {
+            return Err(format!("Cannot canonicalize non-existent path: {path}"));
+        }
+        let canonical = fs::canonicalize(path).map_err(|e| e.to_string())?;
+        Ok(canonical.to_string_lossy().into_owned())
+    }
+    fn absolute(&self, path: &str) -> Result<String, String> {
+        use std::path::Component;
+        let path = Path::new(path);
+        let joined = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            env::current_dir().map_err(|e| e.to_string())?.join(path)
+        };
+        let mut absolute = PathBuf::new();
+        for component in joined.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => _ = absolute.pop(),
+                component => absolute.push(component),
+            }
+        }
+        Ok(absolute.to_string_lossy().into_owned())
+    }
     fn is_file(&self, path: &str) -> Result<bool, String> {
         fs::metadata(path)
             .map(|m| m.is_file())
             .map_err(|e| e.to_string())
     }
+    fn file_size(&self, path: &str) -> Result<u64, String> {
+        let meta = fs::metadata(path).map_err(|e| e.to_string())?;
+        if meta.is_dir() {
+            return Err(format!("Cannot get the size of directory {path}"));
+        }
+        Ok(meta.len())
+    }
+    #[cfg(feature = "zip")]
+    fn read_zip(&self, path: &str) -> Result<Vec<(String, Vec<u8>)>, String> {
+        let file = File::open(path).map_err(|e| format!("{e} {path}"))?;
+        let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+        let mut entries = Vec::with_capacity(archive.len());
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
+            if entry.is_dir() {
+                continue;
+            }
+            let mut contents = Vec::with_capacity(entry.size() as usize);
+            (entry.read_to_end(&mut contents)).map_err(|e| e.to_string())?;
+            entries.push((entry.name().to_string(), contents));
+        }
+        Ok(entries)
+    }
+    #[cfg(feature = "zip")]
+    fn write_zip(&self, path: &str, entries: &[(String, Vec<u8>)]) -> Result<(), String> {
+        let file = File::create(path).map_err(|e| format!("{e} {path}"))?;
+        let mut archive = zip::ZipWriter::new(file);
+        let options = zip::write::FileOptions::default();
+        for (name, contents) in entries {
+            (archive.start_file(name.as_str(), options)).map_err(|e| e.to_string())?;
+            archive.write_all(contents).map_err(|e| e.to_string())?;
+        }
+        archive.finish().map_err(|e| e.to_string())?;
+        Ok(())
+    }
     fn list_dir(&self, path: &str) -> Result<Vec<String>, String> {
         let mut paths = Vec::new();
         for entry in fs::read_dir(path).map_err(|e| e.to_string())? {
@@ -406,6 +727,65 @@ impl SysBackend for NativeSys {
         }
         Ok(paths)
     }
+    #[cfg(feature = "walkdir")]
+    fn list_dir_recursive(&self, path: &str, max_depth: usize) -> Result<Vec<String>, String> {
+        let mut walker = walkdir::WalkDir::new(path).min_depth(1);
+        if max_depth > 0 {
+            walker = walker.max_depth(max_depth);
+        }
+        let mut entries = Vec::new();
+        for entry in walker {
+            let entry = entry.map_err(|e| e.to_string())?;
+            entries.push((entry.depth(), entry.into_path()));
+        }
+        // Sorting by depth first makes the order breadth-first
+        entries.sort();
+        Ok(entries
+            .into_iter()
+            .map(|(_, path)| path.to_string_lossy().into())
+            .collect())
+    }
+    #[cfg(feature = "csv")]
+    fn read_csv(&self, path: &str) -> Result<Vec<Vec<String>>, String> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_path(path)
+            .map_err(|e| e.to_string())?;
+        let mut records = Vec::new();
+        for result in reader.records() {
+            let record = result.map_err(|e| e.to_string())?;
+            records.push(record.iter().map(Into::into).collect());
+        }
+        Ok(records)
+    }
+    #[cfg(feature = "csv")]
+    fn write_csv(
+        &self,
+        path: &str,
+        records: Vec<Vec<String>>,
+        delimiter: u8,
+    ) -> Result<(), String> {
+        let mut writer = csv::WriterBuilder::new()
+            .flexible(true)
+            .delimiter(delimiter)
+            .from_path(path)
+            .map_err(|e| e.to_string())?;
+        for record in records {
+            writer.write_record(record).map_err(|e| e.to_string())?;
+        }
+        writer.flush().map_err(|e| e.to_string())
+    }
+    #[cfg(feature = "glob")]
+    fn glob(&self, pattern: &str) -> Result<Vec<String>, String> {
+        let mut paths = Vec::new();
+        for entry in glob::glob(pattern).map_err(|e| e.to_string())? {
+            let path = entry.map_err(|e| e.to_string())?;
+            paths.push(path.to_string_lossy().into());
+        }
+        paths.sort();
+        Ok(paths)
+    }
     fn open_file(&self, path: &Path, write: bool) -> Result<Handle, String> {
         let handle = NATIVE_SYS.new_handle();
         let file = OpenOptions::new()
@@ -431,6 +811,74 @@ impl SysBackend for NativeSys {
     fn make_dir(&self, path: &Path) -> Result<(), String> {
         fs::create_dir_all(path).map_err(|e| e.to_string())
     }
+    fn make_temp_file(&self) -> Result<String, String> {
+        let path = temp_path()?;
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|e| e.to_string())?;
+        Ok(path.to_string_lossy().into_owned())
+    }
+    fn make_temp_dir(&self) -> Result<String, String> {
+        let path = temp_path()?;
+        fs::create_dir(&path).map_err(|e| e.to_string())?;
+        Ok(path.to_string_lossy().into_owned())
+    }
+    #[cfg(feature = "watch")]
+    fn wait_for_change(&self, path: &str) -> Result<(), String> {
+        use notify::{EventKind, RecursiveMode, Watcher};
+        use std::sync::mpsc::channel;
+        let target = Path::new(path);
+        let target = if target.is_absolute() {
+            target.to_path_buf()
+        } else {
+            env::current_dir().map_err(|e| e.to_string())?.join(target)
+        };
+        // Watch the parent directory so that creation and deletion are noticed too
+        let parent = (target.parent())
+            .filter(|parent| parent.exists())
+            .ok_or_else(|| format!("Cannot watch {path} because its directory does not exist"))?;
+        let file_name = target.file_name();
+        let (send, recv) = channel();
+        let mut watcher = notify::recommended_watcher(send).map_err(|e| e.to_string())?;
+        (watcher.watch(parent, RecursiveMode::NonRecursive)).map_err(|e| e.to_string())?;
+        if target.is_dir() {
+            (watcher.watch(&target, RecursiveMode::NonRecursive)).map_err(|e| e.to_string())?;
+        }
+        for event in recv {
+            let event = event.map_err(|e| e.to_string())?;
+            if !matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) {
+                continue;
+            }
+            let relevant = (event.paths.iter())
+                .any(|changed| changed.starts_with(&target) || changed.file_name() == file_name);
+            if relevant {
+                return Ok(());
+            }
+        }
+        Err(format!("Stopped watching {path}"))
+    }
+    fn remove_dir(&self, path: &str, recursive: bool) -> Result<(), String> {
+        if !Path::new(path).is_dir() {
+            return Err(format!("{path} is not a directory"));
+        }
+        if recursive {
+            fs::remove_dir_all(path).map_err(|e| e.to_string())
+        } else {
+            let mut entries = fs::read_dir(path).map_err(|e| e.to_string())?;
+            if entries.next().is_some() {
+                return Err(format!(
+                    "Directory {path} is not empty. \
+                    Pass 1 as the second argument to remove it and its contents."
+                ));
+            }
+            fs::remove_dir(path).map_err(|e| e.to_string())
+        }
+    }
     fn delete(&self, path: &str) -> Result<(), String> {
         let path = Path::new(path);
         if !path.exists() {
@@ -560,14 +1008,42 @@ impl SysBackend for NativeSys {
             Err(e) => Err(format!("Failed to get clipboard provider: {e}")),
         }
     }
+    #[cfg(feature = "notifications")]
+    fn notify(&self, title: &str, body: &str) -> Result<(), String> {
+        notify_rust::Notification::new()
+            .summary(title)
+            .body(body)
+            .auto_icon()
+            .show()
+            .map(drop)
+            .map_err(|e| e.to_string())
+    }
     fn sleep(&self, seconds: f64) -> Result<(), String> {
         sleep(Duration::from_secs_f64(seconds));
         Ok(())
     }
+    fn monotonic_ns(&self) -> Result<u64, String> {
+        Ok(MONOTONIC_ANCHOR.with(|anchor| {
+            let start = anchor.get().unwrap_or_else(|| {
+                let now = Instant::now();
+                anchor.set(Some(now));
+                now
+            });
+            start.elapsed().as_nanos() as u64
+        }))
+    }
+    fn reset_monotonic(&self) -> Result<(), String> {
+        MONOTONIC_ANCHOR.with(|anchor| anchor.set(Some(Instant::now())));
+        Ok(())
+    }
     #[cfg(all(feature = "terminal_image", feature = "image"))]
-    fn show_image(&self, image: image::DynamicImage, _: Option<&str>) -> Result<(), String> {
+    fn show_image(&self, image: image::DynamicImage, label: Option<&str>) -> Result<(), String> {
+        if let Some(label) = label {
+            self.print_str_stdout(&format!("{label}\n"))?;
+        }
         let (width, height) = if let Some((w, h)) = terminal_size() {
-            let (tw, th) = (w as u32, h.saturating_sub(1) as u32);
+            let reserved = 1 + usize::from(label.is_some());
+            let (tw, th) = (w as u32, h.saturating_sub(reserved) as u32);
             let (iw, ih) = (image.width(), (image.height() / 2).max(1));
             let scaled_to_height = (iw * th / ih.max(1), th);
             let scaled_to_width = (tw, ih * tw / iw.max(1));
@@ -835,6 +1311,16 @@ impl SysBackend for NativeSys {
         {
             NATIVE_SYS.hostnames.remove(&handle);
             Ok(())
+        } else if NATIVE_SYS.has_wasm_instance(handle) {
+            #[cfg(feature = "wasm_runtime")]
+            NATIVE_SYS.wasm_instances.remove(&handle);
+            Ok(())
+        } else if NATIVE_SYS.has_spinner(handle) {
+            #[cfg(feature = "spinner")]
+            if let Some((_, spinner)) = NATIVE_SYS.spinners.remove(&handle) {
+                spinner.finish_and_clear();
+            }
+            Ok(())
         } else {
             Err("Invalid stream handle".to_string())
         }
@@ -843,30 +1329,47 @@ impl SysBackend for NativeSys {
     fn invoke(&self, path: &str) -> Result<(), String> {
         open::that(path).map_err(|e| e.to_string())
     }
-    fn run_command_inherit(&self, command: &str, args: &[&str]) -> Result<i32, String> {
-        let status = Command::new(command)
+    fn run_command_inherit(
+        &self,
+        command: &str,
+        args: &[&str],
+        interrupted: InterruptFn,
+    ) -> Result<i32, String> {
+        let mut child = Command::new(command)
             .args(args)
             .spawn()
-            .map_err(|e| e.to_string())?
-            .wait()
             .map_err(|e| e.to_string())?;
+        let status = wait_interruptible(&mut child, &interrupted)?;
         Ok(status.code().unwrap_or(0))
     }
     fn run_command_capture(
         &self,
         command: &str,
         args: &[&str],
+        interrupted: InterruptFn,
     ) -> Result<(i32, String, String), String> {
-        let output = Command::new(command)
-            .args(args)
-            .output()
-            .map_err(|e| e.to_string())?;
+        let output = output_interruptible(Command::new(command).args(args), &interrupted)?;
         Ok((
             output.status.code().unwrap_or(0),
             String::from_utf8_lossy(&output.stdout).into(),
             String::from_utf8_lossy(&output.stderr).into(),
         ))
     }
+    fn shell_exec(&self, command: &str, interrupted: InterruptFn) -> Result<String, String> {
+        #[cfg(windows)]
+        let (shell, flag) = ("cmd", "/C");
+        #[cfg(not(windows))]
+        let (shell, flag) = ("sh", "-c");
+        let output = output_interruptible(Command::new(shell).args([flag, command]), &interrupted)?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(match output.status.code() {
+                Some(code) => format!("Command failed with exit code {code}: {}", stderr.trim()),
+                None => format!("Command was terminated: {}", stderr.trim()),
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into())
+    }
     fn run_command_stream(&self, command: &str, args: &[&str]) -> Result<[Handle; 3], String> {
         let mut child = Command::new(command)
             .args(args)
@@ -908,6 +1411,10 @@ impl SysBackend for NativeSys {
     fn change_directory(&self, path: &str) -> Result<(), String> {
         env::set_current_dir(path).map_err(|e| e.to_string())
     }
+    fn current_directory(&self) -> Result<String, String> {
+        let dir = env::current_dir().map_err(|e| e.to_string())?;
+        Ok(dir.to_string_lossy().into_owned())
+    }
     #[cfg(feature = "tls")]
     fn https_get(&self, request: &str, handle: Handle) -> Result<String, String> {
         use std::io;
@@ -924,18 +1431,9 @@ impl SysBackend for NativeSys {
         let mut buffer = Vec::new();
         let port = stream.peer_addr().map(|a| a.port());
         if let Ok(443) = port {
-            static CLIENT_CONFIG: Lazy<std::sync::Arc<rustls::ClientConfig>> = Lazy::new(|| {
-                let mut store = rustls::RootCertStore::empty();
-                store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
-                rustls::ClientConfig::builder()
-                    .with_root_certificates(store)
-                    .with_no_client_auth()
-                    .into()
-            });
-
             let server_name =
                 rustls::pki_types::ServerName::try_from(host).map_err(|e| e.to_string())?;
-            let mut conn = rustls::ClientConnection::new(CLIENT_CONFIG.clone(), server_name)
+            let mut conn = rustls::ClientConnection::new(TLS_CLIENT_CONFIG.clone(), server_name)
                 .map_err(|e| e.to_string())?;
             let mut tls = rustls::Stream::new(&mut conn, &mut stream);
             tls.write_all(request.as_bytes())
@@ -956,6 +1454,89 @@ impl SysBackend for NativeSys {
 
         Ok(s)
     }
+    #[cfg(feature = "tls")]
+    fn http_get(&self, url: &str, interrupted: InterruptFn) -> Result<(u16, Vec<u8>), String> {
+        use std::io;
+
+        let url = url.split('#').next().unwrap_or(url);
+        let (is_https, rest) = if let Some(rest) = url.strip_prefix("https://") {
+            (true, rest)
+        } else if let Some(rest) = url.strip_prefix("http://") {
+            (false, rest)
+        } else {
+            return Err(format!(
+                "URL must start with http:// or https://, but it is {url:?}"
+            ));
+        };
+        let (authority, path) = match rest.find(['/', '?']) {
+            Some(i) if rest[i..].starts_with('?') => (&rest[..i], format!("/{}", &rest[i..])),
+            Some(i) => (&rest[..i], rest[i..].to_string()),
+            None => (rest, "/".into()),
+        };
+        let (host, port) = match authority.strip_prefix('[') {
+            // IPv6 addresses are enclosed in brackets
+            Some(rest) => match rest.split_once(']') {
+                Some((host, "")) => (host, None),
+                Some((host, port)) if port.starts_with(':') => (host, Some(&port[1..])),
+                _ => return Err(format!("Invalid host in URL {url:?}")),
+            },
+            None => match authority.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            },
+        };
+        let port = match port {
+            Some(port) => {
+                (port.parse::<u16>()).map_err(|_| format!("Invalid port in URL {url:?}"))?
+            }
+            None if is_https => 443,
+            None => 80,
+        };
+        // The host header includes the port, if any, as it appears in the URL
+        let request = format!("GET {path} HTTP/1.0\r\nHost: {authority}\r\n\r\n");
+
+        let addrs = (host, port).to_socket_addrs().map_err(|e| e.to_string())?;
+        let mut res = Err(format!("Could not resolve host {host:?}"));
+        for addr in addrs {
+            res =
+                TcpStream::connect_timeout(&addr, HTTP_CONNECT_TIMEOUT).map_err(|e| e.to_string());
+            if res.is_ok() {
+                break;
+            }
+        }
+        let stream = res?;
+        let mut stream =
+            InterruptibleStream::new(stream, interrupted).map_err(|e| e.to_string())?;
+        let mut buffer = Vec::new();
+        if is_https {
+            let server_name = rustls::pki_types::ServerName::try_from(host.to_string())
+                .map_err(|e| e.to_string())?;
+            let mut conn = rustls::ClientConnection::new(TLS_CLIENT_CONFIG.clone(), server_name)
+                .map_err(|e| e.to_string())?;
+            let mut tls = rustls::Stream::new(&mut conn, &mut stream);
+            tls.write_all(request.as_bytes())
+                .map_err(|e| e.to_string())?;
+            match tls.read_to_end(&mut buffer) {
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {}
+                Err(e) => return Err(e.to_string()),
+            }
+        } else {
+            (stream.write_all(request.as_bytes())).map_err(|e| e.to_string())?;
+            stream.read_to_end(&mut buffer).map_err(|e| e.to_string())?;
+        }
+
+        let mut headers = [httparse::EMPTY_HEADER; 64];
+        let mut response = httparse::Response::new(&mut headers);
+        match response.parse(&buffer) {
+            Ok(httparse::Status::Complete(len)) => {
+                let status = response.code.unwrap_or_default();
+                Ok((status, buffer.split_off(len)))
+            }
+            Ok(httparse::Status::Partial) => Err("Incomplete HTTP response".into()),
+            Err(e) => Err(format!("Invalid HTTP response: {e}")),
+        }
+    }
     #[cfg(feature = "webcam")]
     fn webcam_capture(&self, index: usize) -> Result<crate::WebcamImage, String> {
         let cam_channels = &NATIVE_SYS.cam_channels;
@@ -1012,6 +1593,205 @@ impl SysBackend for NativeSys {
         crate::ffi_free(ptr);
         Ok(())
     }
+    fn gen_uuid(&self) -> String {
+        uuid::Uuid::new_v4().to_string()
+    }
+    #[cfg(feature = "spinner")]
+    fn spinner_start(&self, message: &str) -> Result<Handle, String> {
+        let spinner = indicatif::ProgressBar::new_spinner().with_message(message.to_string());
+        if !output_enabled() {
+            spinner.set_draw_target(indicatif::ProgressDrawTarget::hidden());
+        }
+        spinner.enable_steady_tick(Duration::from_millis(100));
+        let handle = NATIVE_SYS.new_handle();
+        NATIVE_SYS.spinners.insert(handle, spinner);
+        Ok(handle)
+    }
+    #[cfg(feature = "spinner")]
+    fn spinner_message(&self, handle: Handle, message: &str) -> Result<(), String> {
+        let spinner = (NATIVE_SYS.spinners.get(&handle)).ok_or("Invalid spinner handle")?;
+        spinner.set_message(message.to_string());
+        Ok(())
+    }
+    #[cfg(feature = "spinner")]
+    fn spinner_done(&self, handle: Handle, message: &str) -> Result<(), String> {
+        let (_, spinner) = (NATIVE_SYS.spinners.remove(&handle)).ok_or("Invalid spinner handle")?;
+        spinner.finish_with_message(message.to_string());
+        Ok(())
+    }
+    #[cfg(feature = "wasm_runtime")]
+    fn wasm_load(&self, bytes: &[u8], interrupted: crate::InterruptFn) -> Result<Handle, String> {
+        use wasmtime::*;
+        let module = Module::new(&WASM_ENGINE, bytes).map_err(|e| e.to_string())?;
+        if let Some(import) = module.imports().next() {
+            return Err(format!(
+                "WebAssembly module imports `{}::{}`, but modules \
+                are not allowed to import anything",
+                import.module(),
+                import.name()
+            ));
+        }
+        let mut store = wasm_store(interrupted);
+        let running = WasmRunning::start();
+        let instance = Instance::new(&mut store, &module, &[]).map_err(|e| e.to_string())?;
+        drop(running);
+        let handle = NATIVE_SYS.new_handle();
+        let wasm = WasmInstance { store, instance };
+        (NATIVE_SYS.wasm_instances).insert(handle, Arc::new(parking_lot::Mutex::new(wasm)));
+        Ok(handle)
+    }
+    #[cfg(feature = "wasm_runtime")]
+    fn wasm_call(
+        &self,
+        handle: Handle,
+        name: &str,
+        args: &[f64],
+        interrupted: crate::InterruptFn,
+    ) -> Result<Vec<f64>, String> {
+        use wasmtime::*;
+        // The map is not locked while the function runs, so other handles can still be used
+        let wasm = (NATIVE_SYS.wasm_instances.get(&handle))
+            .map(|wasm| wasm.clone())
+            .ok_or_else(|| "Invalid wasm module handle".to_string())?;
+        let mut wasm = wasm.lock();
+        let WasmInstance { store, instance } = &mut *wasm;
+        *store.data_mut() = interrupted;
+        store.set_epoch_deadline(1);
+        let func = (instance.get_func(&mut *store, name))
+            .ok_or_else(|| format!("WebAssembly module has no exported function `{name}`"))?;
+        let ty = func.ty(&*store);
+        if ty.params().len() != args.len() {
+            return Err(format!(
+                "WebAssembly function `{name}` expects {} argument(s), but {} were given",
+                ty.params().len(),
+                args.len()
+            ));
+        }
+        let params = (ty.params().zip(args))
+            .map(|(ty, &arg)| match ty {
+                ValType::I32 => Ok(Val::I32(arg as i32)),
+                ValType::I64 => Ok(Val::I64(arg as i64)),
+                ValType::F32 => Ok(Val::F32((arg as f32).to_bits())),
+                ValType::F64 => Ok(Val::F64(arg.to_bits())),
+                ty => Err(format!("WebAssembly parameter type {ty} is not supported")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut results = vec![Val::I32(0); ty.results().len()];
+        let running = WasmRunning::start();
+        (func.call(&mut *store, &params, &mut results)).map_err(|e| e.to_string())?;
+        drop(running);
+        results
+            .into_iter()
+            .map(|val| match val {
+                Val::I32(n) => Ok(n as f64),
+                Val::I64(n) => Ok(n as f64),
+                Val::F32(bits) => Ok(f32::from_bits(bits) as f64),
+                Val::F64(bits) => Ok(f64::from_bits(bits)),
+                val => Err(format!(
+                    "WebAssembly result type {} is not supported",
+                    val.ty(&*store)
+                        .map_or("unknown".into(), |ty| ty.to_string())
+                )),
+            })
+            .collect()
+    }
+    #[cfg(feature = "gpu")]
+    fn gpu_map(&self, op: &str, data: &mut [f64]) -> Result<(), String> {
+        use wgpu::{util::DeviceExt, *};
+        const WORKGROUP_SIZE: usize = 64;
+        static GPU: Lazy<Result<(Device, Queue), String>> = Lazy::new(|| {
+            let instance = Instance::default();
+            let adapter =
+                pollster::block_on(instance.request_adapter(&RequestAdapterOptions::default()))
+                    .ok_or("No GPU adapter is available")?;
+            pollster::block_on(adapter.request_device(&DeviceDescriptor::default(), None))
+                .map_err(|e| e.to_string())
+        });
+        if !["sin", "cos", "exp", "sqrt"].contains(&op) {
+            return Err(format!("Unknown GPU function `{op}`"));
+        }
+        if data.is_empty() {
+            return Ok(());
+        }
+        let (device, queue) = GPU.as_ref().map_err(Clone::clone)?;
+        let shader = device.create_shader_module(ShaderModuleDescriptor {
+            label: None,
+            source: ShaderSource::Wgsl(
+                format!(
+                    "@group(0) @binding(0) var<storage, read_write> data: array<f32>;
+                    @compute @workgroup_size({WORKGROUP_SIZE})
+                    fn main(
+                        @builtin(global_invocation_id) id: vec3<u32>,
+                        @builtin(num_workgroups) groups: vec3<u32>,
+                    ) {{
+                        let i = id.x + id.y * groups.x * {WORKGROUP_SIZE}u;
+                        if i < arrayLength(&data) {{
+                            data[i] = {op}(data[i]);
+                        }}
+                    }}"
+                )
+                .into(),
+            ),
+        });
+        let pipeline = device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &shader,
+            entry_point: "main",
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        let limits = device.limits();
+        let max_groups = limits.max_compute_workgroups_per_dimension as usize;
+        let chunk_len = (limits.max_storage_buffer_binding_size as usize / 4)
+            .min(max_groups * max_groups * WORKGROUP_SIZE);
+        for chunk in data.chunks_mut(chunk_len) {
+            let bytes: Vec<u8> = (chunk.iter())
+                .flat_map(|&n| (n as f32).to_le_bytes())
+                .collect();
+            let buffer = device.create_buffer_init(&util::BufferInitDescriptor {
+                label: None,
+                contents: &bytes,
+                usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC,
+            });
+            let staging = device.create_buffer(&BufferDescriptor {
+                label: None,
+                size: bytes.len() as u64,
+                usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            let bind_group = device.create_bind_group(&BindGroupDescriptor {
+                label: None,
+                layout: &pipeline.get_bind_group_layout(0),
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                }],
+            });
+            let groups = chunk.len().div_ceil(WORKGROUP_SIZE);
+            let groups_x = groups.min(max_groups);
+            let groups_y = groups.div_ceil(groups_x);
+            let mut encoder = device.create_command_encoder(&Default::default());
+            {
+                let mut pass = encoder.begin_compute_pass(&Default::default());
+                pass.set_pipeline(&pipeline);
+                pass.set_bind_group(0, &bind_group, &[]);
+                pass.dispatch_workgroups(groups_x as u32, groups_y as u32, 1);
+            }
+            encoder.copy_buffer_to_buffer(&buffer, 0, &staging, 0, bytes.len() as u64);
+            queue.submit(Some(encoder.finish()));
+            let slice = staging.slice(..);
+            let (send, recv) = std::sync::mpsc::channel();
+            slice.map_async(MapMode::Read, move |res| _ = send.send(res));
+            device.poll(Maintain::Wait);
+            (recv.recv().map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+            let mapped = slice.get_mapped_range();
+            for (n, bytes) in chunk.iter_mut().zip(mapped.chunks_exact(4)) {
+                *n = f32::from_le_bytes(bytes.try_into().unwrap()) as f64;
+            }
+        }
+        Ok(())
+    }
     fn load_git_module(&self, url: &str, target: GitTarget) -> Result<PathBuf, String> {
         if let Some(path) = NATIVE_SYS.git_paths.get(url) {
             if path.is_err() || path.as_ref().unwrap().exists() {
@@ -1108,6 +1888,16 @@ impl SysBackend for NativeSys {
     }
 }
 
+#[cfg(feature = "tls")]
+static TLS_CLIENT_CONFIG: Lazy<Arc<rustls::ClientConfig>> = Lazy::new(|| {
+    let mut store = rustls::RootCertStore::empty();
+    store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    rustls::ClientConfig::builder()
+        .with_root_certificates(store)
+        .with_no_client_auth()
+        .into()
+});
+
 /// Takes an HTTP request, validates it, and fixes it (if possible) by adding
 /// the HTTP version and trailing newlines if they aren't present.
 ///
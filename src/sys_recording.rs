@@ -0,0 +1,716 @@
+//! A system backend wrapper that records calls
+
+use std::{
+    any::Any,
+    fmt,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+#[cfg(feature = "image")]
+use image::DynamicImage;
+use parking_lot::Mutex;
+
+use crate::{
+    AudioStreamFn, FfiType, GitTarget, Handle, InterruptFn, LogLevel, SysBackend, Value,
+    WebcamImage,
+};
+
+/// A call to a [`SysBackend`] method, as recorded by [`RecordingSys`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SysCall {
+    /// The name of the method
+    pub method: &'static str,
+    /// The formatted arguments
+    ///
+    /// Strings and paths are kept as-is. Byte buffers are summarized by their length.
+    pub args: Vec<String>,
+    /// The formatted result
+    pub result: Result<String, String>,
+}
+
+/// A system backend that wraps another and records every call made to it
+///
+/// This is useful for testing what IO a piece of code does.
+///
+/// ```rust
+/// use uiua::*;
+///
+/// let mut env = Uiua::with_backend(RecordingSys::new(SafeSys::new()));
+/// env.run_str(r#"&p "Hello!""#).unwrap();
+/// let backend = env.downcast_backend::<RecordingSys<SafeSys>>().unwrap();
+/// let calls = backend.calls();
+/// let print = calls.iter().find(|call| call.method == "print_str_stdout").unwrap();
+/// assert_eq!(print.args, ["Hello!"]);
+/// ```
+pub struct RecordingSys<B> {
+    inner: B,
+    calls: Mutex<Vec<SysCall>>,
+}
+
+impl<B: SysBackend> RecordingSys<B> {
+    /// Wrap a backend
+    pub fn new(inner: B) -> Self {
+        RecordingSys {
+            inner,
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+    /// Get a reference to the wrapped backend
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+    /// Get the calls recorded so far
+    pub fn calls(&self) -> Vec<SysCall> {
+        self.calls.lock().clone()
+    }
+    /// Take the calls recorded so far, clearing the record
+    pub fn take_calls(&self) -> Vec<SysCall> {
+        std::mem::take(&mut *self.calls.lock())
+    }
+    fn record(&self, method: &'static str, args: Vec<String>, result: Result<String, String>) {
+        self.calls.lock().push(SysCall {
+            method,
+            args,
+            result,
+        });
+    }
+}
+
+fn debug_result<T: fmt::Debug>(res: &Result<T, String>) -> Result<String, String> {
+    res.as_ref()
+        .map(|val| format!("{val:?}"))
+        .map_err(Clone::clone)
+}
+
+impl<B: SysBackend> SysBackend for RecordingSys<B> {
+    fn any(&self) -> &dyn Any {
+        self
+    }
+    fn any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn save_error_color(&self, message: String, colored: String) {
+        let call_args = vec![message.clone(), colored.clone()];
+        self.inner.save_error_color(message, colored);
+        self.record("save_error_color", call_args, Ok("()".into()));
+    }
+    fn print_str_stdout(&self, s: &str) -> Result<(), String> {
+        let call_args = vec![s.into()];
+        let res = self.inner.print_str_stdout(s);
+        self.record("print_str_stdout", call_args, debug_result(&res));
+        res
+    }
+    fn print_str_stderr(&self, s: &str) -> Result<(), String> {
+        let call_args = vec![s.into()];
+        let res = self.inner.print_str_stderr(s);
+        self.record("print_str_stderr", call_args, debug_result(&res));
+        res
+    }
+    fn spinner_start(&self, message: &str) -> Result<Handle, String> {
+        let call_args = vec![message.into()];
+        let res = self.inner.spinner_start(message);
+        self.record("spinner_start", call_args, debug_result(&res));
+        res
+    }
+    fn spinner_message(&self, handle: Handle, message: &str) -> Result<(), String> {
+        let call_args = vec![format!("{handle:?}"), message.into()];
+        let res = self.inner.spinner_message(handle, message);
+        self.record("spinner_message", call_args, debug_result(&res));
+        res
+    }
+    fn spinner_done(&self, handle: Handle, message: &str) -> Result<(), String> {
+        let call_args = vec![format!("{handle:?}"), message.into()];
+        let res = self.inner.spinner_done(handle, message);
+        self.record("spinner_done", call_args, debug_result(&res));
+        res
+    }
+    fn gen_uuid(&self) -> String {
+        let call_args = Vec::new();
+        let res = self.inner.gen_uuid();
+        self.record("gen_uuid", call_args, Ok(format!("{res:?}")));
+        res
+    }
+    fn log(&self, level: LogLevel, message: &str) -> Result<(), String> {
+        let call_args = vec![format!("{level:?}"), message.into()];
+        let res = self.inner.log(level, message);
+        self.record("log", call_args, debug_result(&res));
+        res
+    }
+    fn print_str_trace(&self, s: &str) {
+        let call_args = vec![s.into()];
+        self.inner.print_str_trace(s);
+        self.record("print_str_trace", call_args, Ok("()".into()));
+    }
+    fn scan_line_stdin(&self) -> Result<Option<String>, String> {
+        let call_args = Vec::new();
+        let res = self.inner.scan_line_stdin();
+        self.record("scan_line_stdin", call_args, debug_result(&res));
+        res
+    }
+    fn scan_password(&self) -> Result<Option<String>, String> {
+        let res = self.inner.scan_password();
+        let recorded = (res.as_ref())
+            .map(|pwd| if pwd.is_some() { "<password>" } else { "None" }.into())
+            .map_err(Clone::clone);
+        self.record("scan_password", Vec::new(), recorded);
+        res
+    }
+    fn scan_stdin(&self, count: usize) -> Result<Vec<u8>, String> {
+        let call_args = vec![format!("{count:?}")];
+        let res = self.inner.scan_stdin(count);
+        self.record(
+            "scan_stdin",
+            call_args,
+            res.as_ref()
+                .map(|bytes| format!("{} bytes", bytes.len()))
+                .map_err(Clone::clone),
+        );
+        res
+    }
+    fn scan_until_stdin(&self, delim: &[u8]) -> Result<Vec<u8>, String> {
+        let call_args = vec![format!("{} bytes", delim.len())];
+        let res = self.inner.scan_until_stdin(delim);
+        self.record(
+            "scan_until_stdin",
+            call_args,
+            res.as_ref()
+                .map(|bytes| format!("{} bytes", bytes.len()))
+                .map_err(Clone::clone),
+        );
+        res
+    }
+    fn set_raw_mode(&self, raw_mode: bool) -> Result<(), String> {
+        let call_args = vec![format!("{raw_mode:?}")];
+        let res = self.inner.set_raw_mode(raw_mode);
+        self.record("set_raw_mode", call_args, debug_result(&res));
+        res
+    }
+    fn clear_screen(&self) -> Result<(), String> {
+        let res = self.inner.clear_screen();
+        self.record("clear_screen", Vec::new(), debug_result(&res));
+        res
+    }
+    fn var(&self, name: &str) -> Option<String> {
+        let call_args = vec![name.into()];
+        let res = self.inner.var(name);
+        self.record("var", call_args, Ok(format!("{res:?}")));
+        res
+    }
+    fn is_tty(&self) -> bool {
+        let res = self.inner.is_tty();
+        self.record("is_tty", Vec::new(), Ok(format!("{res:?}")));
+        res
+    }
+    fn cpu_count(&self) -> Result<usize, String> {
+        let res = self.inner.cpu_count();
+        self.record("cpu_count", Vec::new(), debug_result(&res));
+        res
+    }
+    fn resident_bytes(&self) -> u64 {
+        let res = self.inner.resident_bytes();
+        self.record("resident_bytes", Vec::new(), Ok(format!("{res:?}")));
+        res
+    }
+    fn term_size(&self) -> Result<(usize, usize), String> {
+        let call_args = Vec::new();
+        let res = self.inner.term_size();
+        self.record("term_size", call_args, debug_result(&res));
+        res
+    }
+    fn exit(&self, status: i32) -> Result<(), String> {
+        let call_args = vec![format!("{status:?}")];
+        let res = self.inner.exit(status);
+        self.record("exit", call_args, debug_result(&res));
+        res
+    }
+    fn file_exists(&self, path: &str) -> bool {
+        let call_args = vec![path.into()];
+        let res = self.inner.file_exists(path);
+        self.record("file_exists", call_args, Ok(format!("{res:?}")));
+        res
+    }
+    fn list_dir(&self, path: &str) -> Result<Vec<String>, String> {
+        let call_args = vec![path.into()];
+        let res = self.inner.list_dir(path);
+        self.record("list_dir", call_args, debug_result(&res));
+        res
+    }
+    fn list_dir_recursive(&self, path: &str, max_depth: usize) -> Result<Vec<String>, String> {
+        let call_args = vec![path.into(), max_depth.to_string()];
+        let res = self.inner.list_dir_recursive(path, max_depth);
+        self.record("list_dir_recursive", call_args, debug_result(&res));
+        res
+    }
+    fn read_csv(&self, path: &str) -> Result<Vec<Vec<String>>, String> {
+        let call_args = vec![path.into()];
+        let res = self.inner.read_csv(path);
+        self.record("read_csv", call_args, debug_result(&res));
+        res
+    }
+    fn write_csv(
+        &self,
+        path: &str,
+        records: Vec<Vec<String>>,
+        delimiter: u8,
+    ) -> Result<(), String> {
+        let call_args = vec![
+            path.into(),
+            format!("{records:?}"),
+            (delimiter as char).into(),
+        ];
+        let res = self.inner.write_csv(path, records, delimiter);
+        self.record("write_csv", call_args, debug_result(&res));
+        res
+    }
+    fn glob(&self, pattern: &str) -> Result<Vec<String>, String> {
+        let call_args = vec![pattern.into()];
+        let res = self.inner.glob(pattern);
+        self.record("glob", call_args, debug_result(&res));
+        res
+    }
+    fn canonicalize(&self, path: &str) -> Result<String, String> {
+        let call_args = vec![path.into()];
+        let res = self.inner.canonicalize(path);
+        self.record("canonicalize", call_args, debug_result(&res));
+        res
+    }
+    fn absolute(&self, path: &str) -> Result<String, String> {
+        let call_args = vec![path.into()];
+        let res = self.inner.absolute(path);
+        self.record("absolute", call_args, debug_result(&res));
+        res
+    }
+    fn is_file(&self, path: &str) -> Result<bool, String> {
+        let call_args = vec![path.into()];
+        let res = self.inner.is_file(path);
+        self.record("is_file", call_args, debug_result(&res));
+        res
+    }
+    fn file_size(&self, path: &str) -> Result<u64, String> {
+        let call_args = vec![path.into()];
+        let res = self.inner.file_size(path);
+        self.record("file_size", call_args, debug_result(&res));
+        res
+    }
+    fn delete(&self, path: &str) -> Result<(), String> {
+        let call_args = vec![path.into()];
+        let res = self.inner.delete(path);
+        self.record("delete", call_args, debug_result(&res));
+        res
+    }
+    fn trash(&self, path: &str) -> Result<(), String> {
+        let call_args = vec![path.into()];
+        let res = self.inner.trash(path);
+        self.record("trash", call_args, debug_result(&res));
+        res
+    }
+    fn read(&self, handle: Handle, count: usize) -> Result<Vec<u8>, String> {
+        let call_args = vec![format!("{handle:?}"), format!("{count:?}")];
+        let res = self.inner.read(handle, count);
+        self.record(
+            "read",
+            call_args,
+            res.as_ref()
+                .map(|bytes| format!("{} bytes", bytes.len()))
+                .map_err(Clone::clone),
+        );
+        res
+    }
+    fn read_all(&self, handle: Handle) -> Result<Vec<u8>, String> {
+        let call_args = vec![format!("{handle:?}")];
+        let res = self.inner.read_all(handle);
+        self.record(
+            "read_all",
+            call_args,
+            res.as_ref()
+                .map(|bytes| format!("{} bytes", bytes.len()))
+                .map_err(Clone::clone),
+        );
+        res
+    }
+    fn read_until(&self, handle: Handle, delim: &[u8]) -> Result<Vec<u8>, String> {
+        let call_args = vec![format!("{handle:?}"), format!("{} bytes", delim.len())];
+        let res = self.inner.read_until(handle, delim);
+        self.record(
+            "read_until",
+            call_args,
+            res.as_ref()
+                .map(|bytes| format!("{} bytes", bytes.len()))
+                .map_err(Clone::clone),
+        );
+        res
+    }
+    fn write(&self, handle: Handle, contents: &[u8]) -> Result<(), String> {
+        let call_args = vec![format!("{handle:?}"), format!("{} bytes", contents.len())];
+        let res = self.inner.write(handle, contents);
+        self.record("write", call_args, debug_result(&res));
+        res
+    }
+    fn create_file(&self, path: &Path) -> Result<Handle, String> {
+        let call_args = vec![path.display().to_string()];
+        let res = self.inner.create_file(path);
+        self.record("create_file", call_args, debug_result(&res));
+        res
+    }
+    fn open_file(&self, path: &Path, write: bool) -> Result<Handle, String> {
+        let call_args = vec![path.display().to_string(), format!("{write:?}")];
+        let res = self.inner.open_file(path, write);
+        self.record("open_file", call_args, debug_result(&res));
+        res
+    }
+    fn make_dir(&self, path: &Path) -> Result<(), String> {
+        let call_args = vec![path.display().to_string()];
+        let res = self.inner.make_dir(path);
+        self.record("make_dir", call_args, debug_result(&res));
+        res
+    }
+    fn remove_dir(&self, path: &str, recursive: bool) -> Result<(), String> {
+        let call_args = vec![path.into(), format!("{recursive:?}")];
+        let res = self.inner.remove_dir(path, recursive);
+        self.record("remove_dir", call_args, debug_result(&res));
+        res
+    }
+    fn wait_for_change(&self, path: &str) -> Result<(), String> {
+        let call_args = vec![path.into()];
+        let res = self.inner.wait_for_change(path);
+        self.record("wait_for_change", call_args, debug_result(&res));
+        res
+    }
+    fn make_temp_file(&self) -> Result<String, String> {
+        let call_args = Vec::new();
+        let res = self.inner.make_temp_file();
+        self.record("make_temp_file", call_args, debug_result(&res));
+        res
+    }
+    fn make_temp_dir(&self) -> Result<String, String> {
+        let call_args = Vec::new();
+        let res = self.inner.make_temp_dir();
+        self.record("make_temp_dir", call_args, debug_result(&res));
+        res
+    }
+    fn file_read_all(&self, path: &Path) -> Result<Vec<u8>, String> {
+        let call_args = vec![path.display().to_string()];
+        let res = self.inner.file_read_all(path);
+        self.record(
+            "file_read_all",
+            call_args,
+            res.as_ref()
+                .map(|bytes| format!("{} bytes", bytes.len()))
+                .map_err(Clone::clone),
+        );
+        res
+    }
+    fn file_write_all(&self, path: &Path, contents: &[u8]) -> Result<(), String> {
+        let call_args = vec![
+            path.display().to_string(),
+            format!("{} bytes", contents.len()),
+        ];
+        let res = self.inner.file_write_all(path, contents);
+        self.record("file_write_all", call_args, debug_result(&res));
+        res
+    }
+    fn read_zip(&self, path: &str) -> Result<Vec<(String, Vec<u8>)>, String> {
+        let call_args = vec![path.into()];
+        let res = self.inner.read_zip(path);
+        self.record(
+            "read_zip",
+            call_args,
+            res.as_ref()
+                .map(|entries| format!("{} entries", entries.len()))
+                .map_err(Clone::clone),
+        );
+        res
+    }
+    fn write_zip(&self, path: &str, entries: &[(String, Vec<u8>)]) -> Result<(), String> {
+        let call_args = vec![path.into(), format!("{} entries", entries.len())];
+        let res = self.inner.write_zip(path, entries);
+        self.record("write_zip", call_args, debug_result(&res));
+        res
+    }
+    fn clipboard(&self) -> Result<String, String> {
+        let call_args = Vec::new();
+        let res = self.inner.clipboard();
+        self.record("clipboard", call_args, debug_result(&res));
+        res
+    }
+    fn set_clipboard(&self, contents: &str) -> Result<(), String> {
+        let call_args = vec![contents.into()];
+        let res = self.inner.set_clipboard(contents);
+        self.record("set_clipboard", call_args, debug_result(&res));
+        res
+    }
+    fn notify(&self, title: &str, body: &str) -> Result<(), String> {
+        let call_args = vec![title.into(), body.into()];
+        let res = self.inner.notify(title, body);
+        self.record("notify", call_args, debug_result(&res));
+        res
+    }
+    fn sleep(&self, seconds: f64) -> Result<(), String> {
+        let call_args = vec![format!("{seconds:?}")];
+        let res = self.inner.sleep(seconds);
+        self.record("sleep", call_args, debug_result(&res));
+        res
+    }
+    #[cfg(feature = "image")]
+    fn show_image(&self, image: DynamicImage, label: Option<&str>) -> Result<(), String> {
+        let call_args = vec![
+            format!("{}×{} image", image.width(), image.height()),
+            format!("{label:?}"),
+        ];
+        let res = self.inner.show_image(image, label);
+        self.record("show_image", call_args, debug_result(&res));
+        res
+    }
+    fn show_gif(&self, gif_bytes: Vec<u8>, label: Option<&str>) -> Result<(), String> {
+        let call_args = vec![format!("{} bytes", gif_bytes.len()), format!("{label:?}")];
+        let res = self.inner.show_gif(gif_bytes, label);
+        self.record("show_gif", call_args, debug_result(&res));
+        res
+    }
+    fn play_audio(&self, wave_bytes: Vec<u8>, label: Option<&str>) -> Result<(), String> {
+        let call_args = vec![format!("{} bytes", wave_bytes.len()), format!("{label:?}")];
+        let res = self.inner.play_audio(wave_bytes, label);
+        self.record("play_audio", call_args, debug_result(&res));
+        res
+    }
+    fn audio_sample_rate(&self) -> u32 {
+        let call_args = Vec::new();
+        let res = self.inner.audio_sample_rate();
+        self.record("audio_sample_rate", call_args, Ok(format!("{res:?}")));
+        res
+    }
+    fn stream_audio(&self, f: AudioStreamFn) -> Result<(), String> {
+        let call_args = vec!["<function>".into()];
+        let res = self.inner.stream_audio(f);
+        self.record("stream_audio", call_args, debug_result(&res));
+        res
+    }
+    fn now(&self) -> f64 {
+        let call_args = Vec::new();
+        let res = self.inner.now();
+        self.record("now", call_args, Ok(format!("{res:?}")));
+        res
+    }
+    fn monotonic_ns(&self) -> Result<u64, String> {
+        let res = self.inner.monotonic_ns();
+        self.record("monotonic_ns", Vec::new(), debug_result(&res));
+        res
+    }
+    fn reset_monotonic(&self) -> Result<(), String> {
+        let res = self.inner.reset_monotonic();
+        self.record("reset_monotonic", Vec::new(), debug_result(&res));
+        res
+    }
+    fn tcp_listen(&self, addr: &str) -> Result<Handle, String> {
+        let call_args = vec![addr.into()];
+        let res = self.inner.tcp_listen(addr);
+        self.record("tcp_listen", call_args, debug_result(&res));
+        res
+    }
+    fn tls_listen(&self, addr: &str, cert: &[u8], key: &[u8]) -> Result<Handle, String> {
+        let call_args = vec![
+            addr.into(),
+            format!("{} bytes", cert.len()),
+            format!("{} bytes", key.len()),
+        ];
+        let res = self.inner.tls_listen(addr, cert, key);
+        self.record("tls_listen", call_args, debug_result(&res));
+        res
+    }
+    fn tcp_accept(&self, handle: Handle) -> Result<Handle, String> {
+        let call_args = vec![format!("{handle:?}")];
+        let res = self.inner.tcp_accept(handle);
+        self.record("tcp_accept", call_args, debug_result(&res));
+        res
+    }
+    fn tcp_connect(&self, addr: &str) -> Result<Handle, String> {
+        let call_args = vec![addr.into()];
+        let res = self.inner.tcp_connect(addr);
+        self.record("tcp_connect", call_args, debug_result(&res));
+        res
+    }
+    fn tls_connect(&self, addr: &str) -> Result<Handle, String> {
+        let call_args = vec![addr.into()];
+        let res = self.inner.tls_connect(addr);
+        self.record("tls_connect", call_args, debug_result(&res));
+        res
+    }
+    fn tcp_addr(&self, handle: Handle) -> Result<SocketAddr, String> {
+        let call_args = vec![format!("{handle:?}")];
+        let res = self.inner.tcp_addr(handle);
+        self.record("tcp_addr", call_args, debug_result(&res));
+        res
+    }
+    fn tcp_set_non_blocking(&self, handle: Handle, non_blocking: bool) -> Result<(), String> {
+        let call_args = vec![format!("{handle:?}"), format!("{non_blocking:?}")];
+        let res = self.inner.tcp_set_non_blocking(handle, non_blocking);
+        self.record("tcp_set_non_blocking", call_args, debug_result(&res));
+        res
+    }
+    fn tcp_set_read_timeout(
+        &self,
+        handle: Handle,
+        timeout: Option<Duration>,
+    ) -> Result<(), String> {
+        let call_args = vec![format!("{handle:?}"), format!("{timeout:?}")];
+        let res = self.inner.tcp_set_read_timeout(handle, timeout);
+        self.record("tcp_set_read_timeout", call_args, debug_result(&res));
+        res
+    }
+    fn tcp_set_write_timeout(
+        &self,
+        handle: Handle,
+        timeout: Option<Duration>,
+    ) -> Result<(), String> {
+        let call_args = vec![format!("{handle:?}"), format!("{timeout:?}")];
+        let res = self.inner.tcp_set_write_timeout(handle, timeout);
+        self.record("tcp_set_write_timeout", call_args, debug_result(&res));
+        res
+    }
+    fn close(&self, handle: Handle) -> Result<(), String> {
+        let call_args = vec![format!("{handle:?}")];
+        let res = self.inner.close(handle);
+        self.record("close", call_args, debug_result(&res));
+        res
+    }
+    fn invoke(&self, path: &str) -> Result<(), String> {
+        let call_args = vec![path.into()];
+        let res = self.inner.invoke(path);
+        self.record("invoke", call_args, debug_result(&res));
+        res
+    }
+    fn run_command_inherit(
+        &self,
+        command: &str,
+        args: &[&str],
+        interrupted: InterruptFn,
+    ) -> Result<i32, String> {
+        let call_args = vec![command.into(), format!("{args:?}")];
+        let res = self.inner.run_command_inherit(command, args, interrupted);
+        self.record("run_command_inherit", call_args, debug_result(&res));
+        res
+    }
+    fn run_command_capture(
+        &self,
+        command: &str,
+        args: &[&str],
+        interrupted: InterruptFn,
+    ) -> Result<(i32, String, String), String> {
+        let call_args = vec![command.into(), format!("{args:?}")];
+        let res = self.inner.run_command_capture(command, args, interrupted);
+        self.record("run_command_capture", call_args, debug_result(&res));
+        res
+    }
+    fn shell_exec(&self, command: &str, interrupted: InterruptFn) -> Result<String, String> {
+        let call_args = vec![command.into()];
+        let res = self.inner.shell_exec(command, interrupted);
+        self.record("shell_exec", call_args, debug_result(&res));
+        res
+    }
+    fn run_command_stream(&self, command: &str, args: &[&str]) -> Result<[Handle; 3], String> {
+        let call_args = vec![command.into(), format!("{args:?}")];
+        let res = self.inner.run_command_stream(command, args);
+        self.record("run_command_stream", call_args, debug_result(&res));
+        res
+    }
+    fn change_directory(&self, path: &str) -> Result<(), String> {
+        let call_args = vec![path.into()];
+        let res = self.inner.change_directory(path);
+        self.record("change_directory", call_args, debug_result(&res));
+        res
+    }
+    fn current_directory(&self) -> Result<String, String> {
+        let call_args = Vec::new();
+        let res = self.inner.current_directory();
+        self.record("current_directory", call_args, debug_result(&res));
+        res
+    }
+    fn https_get(&self, request: &str, handle: Handle) -> Result<String, String> {
+        let call_args = vec![request.into(), format!("{handle:?}")];
+        let res = self.inner.https_get(request, handle);
+        self.record("https_get", call_args, debug_result(&res));
+        res
+    }
+    fn http_get(&self, url: &str, interrupted: InterruptFn) -> Result<(u16, Vec<u8>), String> {
+        let call_args = vec![url.into()];
+        let res = self.inner.http_get(url, interrupted);
+        let recorded = (res.as_ref())
+            .map(|(status, body)| format!("{status}, {} bytes", body.len()))
+            .map_err(Clone::clone);
+        self.record("http_get", call_args, recorded);
+        res
+    }
+    fn webcam_capture(&self, index: usize) -> Result<WebcamImage, String> {
+        let call_args = vec![format!("{index:?}")];
+        let res = self.inner.webcam_capture(index);
+        self.record(
+            "webcam_capture",
+            call_args,
+            res.as_ref().map(|_| "<image>".into()).map_err(Clone::clone),
+        );
+        res
+    }
+    fn ffi(
+        &self,
+        file: &str,
+        result_ty: FfiType,
+        name: &str,
+        arg_tys: &[FfiType],
+        args: &[Value],
+    ) -> Result<Value, String> {
+        let call_args = vec![
+            file.into(),
+            format!("{result_ty:?}"),
+            name.into(),
+            format!("{arg_tys:?}"),
+            format!("{args:?}"),
+        ];
+        let res = self.inner.ffi(file, result_ty, name, arg_tys, args);
+        self.record("ffi", call_args, debug_result(&res));
+        res
+    }
+    fn mem_copy(&self, ty: FfiType, ptr: *const (), len: usize) -> Result<Value, String> {
+        let call_args = vec![format!("{ty:?}"), format!("{ptr:?}"), format!("{len:?}")];
+        let res = self.inner.mem_copy(ty, ptr, len);
+        self.record("mem_copy", call_args, debug_result(&res));
+        res
+    }
+    fn mem_free(&self, ptr: *const ()) -> Result<(), String> {
+        let call_args = vec![format!("{ptr:?}")];
+        let res = self.inner.mem_free(ptr);
+        self.record("mem_free", call_args, debug_result(&res));
+        res
+    }
+    fn wasm_load(&self, bytes: &[u8], interrupted: InterruptFn) -> Result<Handle, String> {
+        let call_args = vec![format!("{} bytes", bytes.len())];
+        let res = self.inner.wasm_load(bytes, interrupted);
+        self.record("wasm_load", call_args, debug_result(&res));
+        res
+    }
+    fn wasm_call(
+        &self,
+        handle: Handle,
+        name: &str,
+        args: &[f64],
+        interrupted: InterruptFn,
+    ) -> Result<Vec<f64>, String> {
+        let call_args = vec![format!("{handle:?}"), name.into(), format!("{args:?}")];
+        let res = self.inner.wasm_call(handle, name, args, interrupted);
+        self.record("wasm_call", call_args, debug_result(&res));
+        res
+    }
+    fn gpu_map(&self, op: &str, data: &mut [f64]) -> Result<(), String> {
+        let call_args = vec![op.into(), format!("{data:?}")];
+        let res = self.inner.gpu_map(op, data);
+        self.record("gpu_map", call_args, debug_result(&res));
+        res
+    }
+    fn load_git_module(&self, url: &str, target: GitTarget) -> Result<PathBuf, String> {
+        let call_args = vec![url.into(), format!("{target:?}")];
+        let res = self.inner.load_git_module(url, target);
+        self.record("load_git_module", call_args, debug_result(&res));
+        res
+    }
+}
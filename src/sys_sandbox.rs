@@ -0,0 +1,650 @@
+//! A system backend wrapper that restricts what IO is allowed
+
+use std::{
+    any::Any,
+    env,
+    net::SocketAddr,
+    path::{Component, Path, PathBuf},
+    time::Duration,
+};
+
+#[cfg(feature = "image")]
+use image::DynamicImage;
+
+use crate::{
+    AudioStreamFn, FfiType, GitTarget, Handle, InterruptFn, LogLevel, SysBackend, Value,
+    WebcamImage,
+};
+
+const NOT_PERMITTED: &str = "Operation not permitted by sandbox policy";
+
+/// A policy that determines what a [`SandboxSys`] allows
+///
+/// The default policy denies all file, network, shell, and environment variable access,
+/// as well as access to the clipboard, webcam, notifications, WebAssembly, and the GPU.
+/// Use [`SandboxPolicy::builder`] to allow more.
+#[derive(Debug, Clone, Default)]
+pub struct SandboxPolicy {
+    read_paths: Vec<PathBuf>,
+    write_paths: Vec<PathBuf>,
+    vars: Vec<String>,
+    network: bool,
+    shell: bool,
+    clipboard: bool,
+    webcam: bool,
+    notify: bool,
+    wasm: bool,
+    gpu: bool,
+}
+
+impl SandboxPolicy {
+    /// Start building a policy that denies everything by default
+    pub fn builder() -> SandboxPolicyBuilder {
+        SandboxPolicyBuilder::default()
+    }
+    /// Get the path prefixes that can be read from
+    pub fn read_paths(&self) -> &[PathBuf] {
+        &self.read_paths
+    }
+    /// Get the path prefixes that can be written to
+    pub fn write_paths(&self) -> &[PathBuf] {
+        &self.write_paths
+    }
+    /// Get the names of the environment variables that can be read
+    pub fn vars(&self) -> &[String] {
+        &self.vars
+    }
+    /// Check whether network operations are allowed
+    pub fn allows_network(&self) -> bool {
+        self.network
+    }
+    /// Check whether running commands is allowed
+    pub fn allows_shell(&self) -> bool {
+        self.shell
+    }
+    /// Check whether the clipboard can be read and written
+    pub fn allows_clipboard(&self) -> bool {
+        self.clipboard
+    }
+    /// Check whether the webcam can be captured
+    pub fn allows_webcam(&self) -> bool {
+        self.webcam
+    }
+    /// Check whether notifications can be sent
+    pub fn allows_notify(&self) -> bool {
+        self.notify
+    }
+    /// Check whether WebAssembly modules can be loaded and called
+    pub fn allows_wasm(&self) -> bool {
+        self.wasm
+    }
+    /// Check whether functions can be run on the GPU
+    pub fn allows_gpu(&self) -> bool {
+        self.gpu
+    }
+}
+
+/// A builder for a [`SandboxPolicy`]
+#[derive(Debug, Clone, Default)]
+pub struct SandboxPolicyBuilder {
+    policy: SandboxPolicy,
+}
+
+impl SandboxPolicyBuilder {
+    /// Allow reading files under a path
+    pub fn allow_read(mut self, path: impl Into<PathBuf>) -> Self {
+        self.policy.read_paths.push(path.into());
+        self
+    }
+    /// Allow reading and writing files under a path
+    pub fn allow_write(mut self, path: impl Into<PathBuf>) -> Self {
+        self.policy.write_paths.push(path.into());
+        self
+    }
+    /// Allow reading an environment variable
+    ///
+    /// Variables that are not allowed read as unset.
+    pub fn allow_var(mut self, name: impl Into<String>) -> Self {
+        self.policy.vars.push(name.into());
+        self
+    }
+    /// Set whether network operations are allowed
+    ///
+    /// This includes TCP and TLS sockets, HTTPS requests, and loading git modules.
+    pub fn allow_network(mut self, allow: bool) -> Self {
+        self.policy.network = allow;
+        self
+    }
+    /// Set whether running commands is allowed
+    ///
+    /// This includes running commands, invoking paths, changing the current directory, and FFI.
+    pub fn allow_shell(mut self, allow: bool) -> Self {
+        self.policy.shell = allow;
+        self
+    }
+    /// Set whether the clipboard can be read and written
+    pub fn allow_clipboard(mut self, allow: bool) -> Self {
+        self.policy.clipboard = allow;
+        self
+    }
+    /// Set whether the webcam can be captured
+    pub fn allow_webcam(mut self, allow: bool) -> Self {
+        self.policy.webcam = allow;
+        self
+    }
+    /// Set whether notifications can be sent
+    pub fn allow_notify(mut self, allow: bool) -> Self {
+        self.policy.notify = allow;
+        self
+    }
+    /// Set whether WebAssembly modules can be loaded and called
+    pub fn allow_wasm(mut self, allow: bool) -> Self {
+        self.policy.wasm = allow;
+        self
+    }
+    /// Set whether functions can be run on the GPU
+    pub fn allow_gpu(mut self, allow: bool) -> Self {
+        self.policy.gpu = allow;
+        self
+    }
+    /// Build the policy
+    pub fn build(self) -> SandboxPolicy {
+        self.policy
+    }
+}
+
+/// A system backend that wraps another and denies operations not allowed by a [`SandboxPolicy`]
+///
+/// Denied operations fail with the error `Operation not permitted by sandbox policy`.
+/// Exiting the process is always denied.
+///
+/// Paths are resolved before being checked against the policy's prefixes.
+/// The longest existing ancestor of a path is canonicalized by the wrapped backend,
+/// and the rest of the path is normalized lexically, so `..` and symlinks cannot be used to escape an allowed directory.
+///
+/// ```rust
+/// use uiua::*;
+///
+/// let sys = SafeSys::new();
+/// sys.add_file("data/in.txt", "hello");
+/// sys.add_file("secret.txt", "shh");
+/// let policy = SandboxPolicy::builder()
+///     .allow_read("data")
+///     .allow_write("out")
+///     .build();
+/// let mut env = Uiua::with_backend(SandboxSys::new(sys, policy));
+/// env.run_str(r#"&fwa "out/copy.txt" &fras "data/in.txt""#).unwrap();
+/// assert!(env.run_str(r#"&fras "data/../secret.txt""#).is_err());
+/// assert!(env.run_str(r#"&fwa "data/in.txt" "bye""#).is_err());
+/// assert!(env.run_str(r#"&sh "ls""#).is_err());
+/// assert!(env.run_str(r#"&cd "data""#).is_err());
+/// ```
+pub struct SandboxSys<B> {
+    inner: B,
+    policy: SandboxPolicy,
+}
+
+impl<B: SysBackend> SandboxSys<B> {
+    /// Wrap a backend with a policy
+    pub fn new(inner: B, policy: SandboxPolicy) -> Self {
+        SandboxSys { inner, policy }
+    }
+    /// Get a reference to the wrapped backend
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+    /// Get the sandbox's policy
+    pub fn policy(&self) -> &SandboxPolicy {
+        &self.policy
+    }
+    fn resolve(&self, path: &Path) -> PathBuf {
+        let components: Vec<Component> = path.components().collect();
+        let mut resolved = PathBuf::new();
+        let mut rest = &components[..];
+        for i in (1..=components.len()).rev() {
+            let ancestor: PathBuf = components[..i].iter().collect();
+            let Some(ancestor) = ancestor.to_str() else {
+                continue;
+            };
+            if let Ok(canonical) = self.inner.canonicalize(ancestor) {
+                resolved = canonical.into();
+                rest = &components[i..];
+                break;
+            }
+        }
+        for component in rest {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if matches!(
+                        resolved.components().next_back(),
+                        None | Some(Component::ParentDir)
+                    ) {
+                        resolved.push("..");
+                    } else {
+                        resolved.pop();
+                    }
+                }
+                component => resolved.push(component),
+            }
+        }
+        resolved
+    }
+    fn is_within(&self, path: &Path, prefixes: &[PathBuf]) -> bool {
+        let path = self.resolve(path);
+        let ups = |path: &Path| {
+            (path.components())
+                .take_while(|c| *c == Component::ParentDir)
+                .count()
+        };
+        prefixes.iter().any(|prefix| {
+            let prefix = self.resolve(prefix);
+            path.starts_with(&prefix) && ups(&path) == ups(&prefix)
+        })
+    }
+    fn check_read(&self, path: &Path) -> Result<(), String> {
+        if self.is_within(path, &self.policy.read_paths)
+            || self.is_within(path, &self.policy.write_paths)
+        {
+            Ok(())
+        } else {
+            Err(NOT_PERMITTED.into())
+        }
+    }
+    fn check_write(&self, path: &Path) -> Result<(), String> {
+        if self.is_within(path, &self.policy.write_paths) {
+            Ok(())
+        } else {
+            Err(NOT_PERMITTED.into())
+        }
+    }
+    fn check_network(&self) -> Result<(), String> {
+        permit(self.policy.network)
+    }
+    fn check_shell(&self) -> Result<(), String> {
+        permit(self.policy.shell)
+    }
+}
+
+fn permit(allowed: bool) -> Result<(), String> {
+    if allowed {
+        Ok(())
+    } else {
+        Err(NOT_PERMITTED.into())
+    }
+}
+
+impl<B: SysBackend> SysBackend for SandboxSys<B> {
+    fn any(&self) -> &dyn Any {
+        self
+    }
+    fn any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn save_error_color(&self, message: String, colored: String) {
+        self.inner.save_error_color(message, colored)
+    }
+    fn print_str_stdout(&self, s: &str) -> Result<(), String> {
+        self.inner.print_str_stdout(s)
+    }
+    fn print_str_stderr(&self, s: &str) -> Result<(), String> {
+        self.inner.print_str_stderr(s)
+    }
+    fn spinner_start(&self, message: &str) -> Result<Handle, String> {
+        self.inner.spinner_start(message)
+    }
+    fn spinner_message(&self, handle: Handle, message: &str) -> Result<(), String> {
+        self.inner.spinner_message(handle, message)
+    }
+    fn spinner_done(&self, handle: Handle, message: &str) -> Result<(), String> {
+        self.inner.spinner_done(handle, message)
+    }
+    fn gen_uuid(&self) -> String {
+        self.inner.gen_uuid()
+    }
+    fn log(&self, level: LogLevel, message: &str) -> Result<(), String> {
+        self.inner.log(level, message)
+    }
+    fn print_str_trace(&self, s: &str) {
+        self.inner.print_str_trace(s)
+    }
+    fn scan_line_stdin(&self) -> Result<Option<String>, String> {
+        self.inner.scan_line_stdin()
+    }
+    fn scan_password(&self) -> Result<Option<String>, String> {
+        self.inner.scan_password()
+    }
+    fn scan_stdin(&self, count: usize) -> Result<Vec<u8>, String> {
+        self.inner.scan_stdin(count)
+    }
+    fn scan_until_stdin(&self, delim: &[u8]) -> Result<Vec<u8>, String> {
+        self.inner.scan_until_stdin(delim)
+    }
+    fn set_raw_mode(&self, raw_mode: bool) -> Result<(), String> {
+        self.inner.set_raw_mode(raw_mode)
+    }
+    fn clear_screen(&self) -> Result<(), String> {
+        self.inner.clear_screen()
+    }
+    fn var(&self, name: &str) -> Option<String> {
+        // Environment variables often hold secrets, so they must be allowed by name
+        if self.policy.vars.iter().any(|var| var == name) {
+            self.inner.var(name)
+        } else {
+            None
+        }
+    }
+    fn is_tty(&self) -> bool {
+        self.inner.is_tty()
+    }
+    fn cpu_count(&self) -> Result<usize, String> {
+        self.inner.cpu_count()
+    }
+    fn resident_bytes(&self) -> u64 {
+        self.inner.resident_bytes()
+    }
+    fn term_size(&self) -> Result<(usize, usize), String> {
+        self.inner.term_size()
+    }
+    fn exit(&self, _status: i32) -> Result<(), String> {
+        Err(NOT_PERMITTED.into())
+    }
+    fn file_exists(&self, path: &str) -> bool {
+        self.check_read(Path::new(path)).is_ok() && self.inner.file_exists(path)
+    }
+    fn list_dir(&self, path: &str) -> Result<Vec<String>, String> {
+        self.check_read(Path::new(path))?;
+        self.inner.list_dir(path)
+    }
+    fn list_dir_recursive(&self, path: &str, max_depth: usize) -> Result<Vec<String>, String> {
+        self.check_read(Path::new(path))?;
+        // Subdirectories may be symlinks that lead outside of the allowed paths
+        let paths = self.inner.list_dir_recursive(path, max_depth)?;
+        Ok(paths
+            .into_iter()
+            .filter(|path| self.check_read(Path::new(path)).is_ok())
+            .collect())
+    }
+    fn read_csv(&self, path: &str) -> Result<Vec<Vec<String>>, String> {
+        self.check_read(Path::new(path))?;
+        self.inner.read_csv(path)
+    }
+    fn write_csv(
+        &self,
+        path: &str,
+        records: Vec<Vec<String>>,
+        delimiter: u8,
+    ) -> Result<(), String> {
+        self.check_write(Path::new(path))?;
+        self.inner.write_csv(path, records, delimiter)
+    }
+    fn glob(&self, pattern: &str) -> Result<Vec<String>, String> {
+        self.check_read(Path::new(pattern))?;
+        // Wildcards may match paths outside of the allowed paths
+        let paths = self.inner.glob(pattern)?;
+        Ok(paths
+            .into_iter()
+            .filter(|path| self.check_read(Path::new(path)).is_ok())
+            .collect())
+    }
+    fn canonicalize(&self, path: &str) -> Result<String, String> {
+        self.check_read(Path::new(path))?;
+        self.inner.canonicalize(path)
+    }
+    fn absolute(&self, path: &str) -> Result<String, String> {
+        self.check_read(Path::new(path))?;
+        self.inner.absolute(path)
+    }
+    fn is_file(&self, path: &str) -> Result<bool, String> {
+        self.check_read(Path::new(path))?;
+        self.inner.is_file(path)
+    }
+    fn file_size(&self, path: &str) -> Result<u64, String> {
+        self.check_read(Path::new(path))?;
+        self.inner.file_size(path)
+    }
+    fn delete(&self, path: &str) -> Result<(), String> {
+        self.check_write(Path::new(path))?;
+        self.inner.delete(path)
+    }
+    fn trash(&self, path: &str) -> Result<(), String> {
+        self.check_write(Path::new(path))?;
+        self.inner.trash(path)
+    }
+    fn read(&self, handle: Handle, count: usize) -> Result<Vec<u8>, String> {
+        self.inner.read(handle, count)
+    }
+    fn read_all(&self, handle: Handle) -> Result<Vec<u8>, String> {
+        self.inner.read_all(handle)
+    }
+    fn read_until(&self, handle: Handle, delim: &[u8]) -> Result<Vec<u8>, String> {
+        self.inner.read_until(handle, delim)
+    }
+    fn write(&self, handle: Handle, contents: &[u8]) -> Result<(), String> {
+        self.inner.write(handle, contents)
+    }
+    fn create_file(&self, path: &Path) -> Result<Handle, String> {
+        self.check_write(path)?;
+        self.inner.create_file(path)
+    }
+    fn open_file(&self, path: &Path, write: bool) -> Result<Handle, String> {
+        if write {
+            self.check_write(path)?;
+        } else {
+            self.check_read(path)?;
+        }
+        self.inner.open_file(path, write)
+    }
+    fn make_dir(&self, path: &Path) -> Result<(), String> {
+        self.check_write(path)?;
+        self.inner.make_dir(path)
+    }
+    fn remove_dir(&self, path: &str, recursive: bool) -> Result<(), String> {
+        self.check_write(Path::new(path))?;
+        self.inner.remove_dir(path, recursive)
+    }
+    fn wait_for_change(&self, path: &str) -> Result<(), String> {
+        self.check_read(Path::new(path))?;
+        self.inner.wait_for_change(path)
+    }
+    fn make_temp_file(&self) -> Result<String, String> {
+        self.check_write(&env::temp_dir())?;
+        self.inner.make_temp_file()
+    }
+    fn make_temp_dir(&self) -> Result<String, String> {
+        self.check_write(&env::temp_dir())?;
+        self.inner.make_temp_dir()
+    }
+    fn file_read_all(&self, path: &Path) -> Result<Vec<u8>, String> {
+        self.check_read(path)?;
+        self.inner.file_read_all(path)
+    }
+    fn file_write_all(&self, path: &Path, contents: &[u8]) -> Result<(), String> {
+        self.check_write(path)?;
+        self.inner.file_write_all(path, contents)
+    }
+    fn read_zip(&self, path: &str) -> Result<Vec<(String, Vec<u8>)>, String> {
+        self.check_read(Path::new(path))?;
+        self.inner.read_zip(path)
+    }
+    fn write_zip(&self, path: &str, entries: &[(String, Vec<u8>)]) -> Result<(), String> {
+        self.check_write(Path::new(path))?;
+        self.inner.write_zip(path, entries)
+    }
+    fn clipboard(&self) -> Result<String, String> {
+        permit(self.policy.clipboard)?;
+        self.inner.clipboard()
+    }
+    fn set_clipboard(&self, contents: &str) -> Result<(), String> {
+        permit(self.policy.clipboard)?;
+        self.inner.set_clipboard(contents)
+    }
+    fn notify(&self, title: &str, body: &str) -> Result<(), String> {
+        permit(self.policy.notify)?;
+        self.inner.notify(title, body)
+    }
+    fn sleep(&self, seconds: f64) -> Result<(), String> {
+        self.inner.sleep(seconds)
+    }
+    #[cfg(feature = "image")]
+    fn show_image(&self, image: DynamicImage, label: Option<&str>) -> Result<(), String> {
+        self.inner.show_image(image, label)
+    }
+    fn show_gif(&self, gif_bytes: Vec<u8>, label: Option<&str>) -> Result<(), String> {
+        self.inner.show_gif(gif_bytes, label)
+    }
+    fn play_audio(&self, wave_bytes: Vec<u8>, label: Option<&str>) -> Result<(), String> {
+        self.inner.play_audio(wave_bytes, label)
+    }
+    fn audio_sample_rate(&self) -> u32 {
+        self.inner.audio_sample_rate()
+    }
+    fn stream_audio(&self, f: AudioStreamFn) -> Result<(), String> {
+        self.inner.stream_audio(f)
+    }
+    fn now(&self) -> f64 {
+        self.inner.now()
+    }
+    fn monotonic_ns(&self) -> Result<u64, String> {
+        self.inner.monotonic_ns()
+    }
+    fn reset_monotonic(&self) -> Result<(), String> {
+        self.inner.reset_monotonic()
+    }
+    fn tcp_listen(&self, addr: &str) -> Result<Handle, String> {
+        self.check_network()?;
+        self.inner.tcp_listen(addr)
+    }
+    fn tls_listen(&self, addr: &str, cert: &[u8], key: &[u8]) -> Result<Handle, String> {
+        self.check_network()?;
+        self.inner.tls_listen(addr, cert, key)
+    }
+    fn tcp_accept(&self, handle: Handle) -> Result<Handle, String> {
+        self.check_network()?;
+        self.inner.tcp_accept(handle)
+    }
+    fn tcp_connect(&self, addr: &str) -> Result<Handle, String> {
+        self.check_network()?;
+        self.inner.tcp_connect(addr)
+    }
+    fn tls_connect(&self, addr: &str) -> Result<Handle, String> {
+        self.check_network()?;
+        self.inner.tls_connect(addr)
+    }
+    fn tcp_addr(&self, handle: Handle) -> Result<SocketAddr, String> {
+        self.inner.tcp_addr(handle)
+    }
+    fn tcp_set_non_blocking(&self, handle: Handle, non_blocking: bool) -> Result<(), String> {
+        self.inner.tcp_set_non_blocking(handle, non_blocking)
+    }
+    fn tcp_set_read_timeout(
+        &self,
+        handle: Handle,
+        timeout: Option<Duration>,
+    ) -> Result<(), String> {
+        self.inner.tcp_set_read_timeout(handle, timeout)
+    }
+    fn tcp_set_write_timeout(
+        &self,
+        handle: Handle,
+        timeout: Option<Duration>,
+    ) -> Result<(), String> {
+        self.inner.tcp_set_write_timeout(handle, timeout)
+    }
+    fn close(&self, handle: Handle) -> Result<(), String> {
+        self.inner.close(handle)
+    }
+    fn invoke(&self, path: &str) -> Result<(), String> {
+        self.check_shell()?;
+        self.inner.invoke(path)
+    }
+    fn run_command_inherit(
+        &self,
+        command: &str,
+        args: &[&str],
+        interrupted: InterruptFn,
+    ) -> Result<i32, String> {
+        self.check_shell()?;
+        self.inner.run_command_inherit(command, args, interrupted)
+    }
+    fn run_command_capture(
+        &self,
+        command: &str,
+        args: &[&str],
+        interrupted: InterruptFn,
+    ) -> Result<(i32, String, String), String> {
+        self.check_shell()?;
+        self.inner.run_command_capture(command, args, interrupted)
+    }
+    fn shell_exec(&self, command: &str, interrupted: InterruptFn) -> Result<String, String> {
+        self.check_shell()?;
+        self.inner.shell_exec(command, interrupted)
+    }
+    fn run_command_stream(&self, command: &str, args: &[&str]) -> Result<[Handle; 3], String> {
+        self.check_shell()?;
+        self.inner.run_command_stream(command, args)
+    }
+    fn change_directory(&self, path: &str) -> Result<(), String> {
+        // Relative policy paths are resolved against the current directory,
+        // so changing it is only allowed along with running commands
+        self.check_shell()?;
+        self.check_read(Path::new(path))?;
+        self.inner.change_directory(path)
+    }
+    fn current_directory(&self) -> Result<String, String> {
+        self.inner.current_directory()
+    }
+    fn https_get(&self, request: &str, handle: Handle) -> Result<String, String> {
+        self.check_network()?;
+        self.inner.https_get(request, handle)
+    }
+    fn http_get(&self, url: &str, interrupted: InterruptFn) -> Result<(u16, Vec<u8>), String> {
+        self.check_network()?;
+        self.inner.http_get(url, interrupted)
+    }
+    fn webcam_capture(&self, index: usize) -> Result<WebcamImage, String> {
+        permit(self.policy.webcam)?;
+        self.inner.webcam_capture(index)
+    }
+    fn ffi(
+        &self,
+        file: &str,
+        result_ty: FfiType,
+        name: &str,
+        arg_tys: &[FfiType],
+        args: &[Value],
+    ) -> Result<Value, String> {
+        self.check_shell()?;
+        self.inner.ffi(file, result_ty, name, arg_tys, args)
+    }
+    fn mem_copy(&self, ty: FfiType, ptr: *const (), len: usize) -> Result<Value, String> {
+        self.check_shell()?;
+        self.inner.mem_copy(ty, ptr, len)
+    }
+    fn mem_free(&self, ptr: *const ()) -> Result<(), String> {
+        self.check_shell()?;
+        self.inner.mem_free(ptr)
+    }
+    fn wasm_load(&self, bytes: &[u8], interrupted: InterruptFn) -> Result<Handle, String> {
+        permit(self.policy.wasm)?;
+        self.inner.wasm_load(bytes, interrupted)
+    }
+    fn wasm_call(
+        &self,
+        handle: Handle,
+        name: &str,
+        args: &[f64],
+        interrupted: InterruptFn,
+    ) -> Result<Vec<f64>, String> {
+        permit(self.policy.wasm)?;
+        self.inner.wasm_call(handle, name, args, interrupted)
+    }
+    fn gpu_map(&self, op: &str, data: &mut [f64]) -> Result<(), String> {
+        permit(self.policy.gpu)?;
+        self.inner.gpu_map(op, data)
+    }
+    fn load_git_module(&self, url: &str, target: GitTarget) -> Result<PathBuf, String> {
+        self.check_network()?;
+        self.inner.load_git_module(url, target)
+    }
+}